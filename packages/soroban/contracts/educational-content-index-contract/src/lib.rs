@@ -14,8 +14,8 @@ use soroban_sdk::{contract, contractimpl, symbol_short, Env, String, Symbol, Vec
 
 use crate::error::Error;
 use crate::events::Events;
-use crate::metadata::Content;
-use crate::search::search_content;
+use crate::metadata::{Content, ContentPage};
+use crate::search::{search_content, ContentFilter, TagQuery};
 use crate::storage::ContentStorage;
 
 const INITIALIZED_KEY: Symbol = symbol_short!("INIT");
@@ -55,7 +55,7 @@ impl ContentSearchContract {
         let results = search_content(&env, subject.clone())?;
 
         // Emit search performed event
-        Events::search_performed(&env, &subject, results.len() as u32);
+        Events::search_performed(&env, &subject, results.len());
 
         Ok(results)
     }
@@ -165,15 +165,87 @@ impl ContentSearchContract {
             }
         }
 
-        let results = crate::search::engine::search_content_multi_tag(&env, tags)?;
+        let results = crate::search::search_content_multi_tag(&env, tags)?;
+
+        // Emit search performed event
+        Events::search_performed(&env, &String::from_str(&env, "multi-tag"), results.len());
+
+        Ok(results)
+    }
+
+    /// Search content with a boolean tag query, e.g. "algebra AND beginner-friendly NOT calculus"
+    pub fn search_content_query(env: Env, query: TagQuery) -> Result<Vec<Content>, Error> {
+        // Verify contract is initialized
+        if !env.storage().instance().has(&INITIALIZED_KEY) {
+            return Err(Error::NotInitialized);
+        }
+
+        // Validate the query
+        if !crate::validate::validate_tag_query(&query) {
+            return Err(Error::InvalidInput);
+        }
+
+        let results = crate::search::search_content_query(&env, &query)?;
+
+        // Emit search performed event
+        Events::search_performed(&env, &String::from_str(&env, "query"), results.len());
+
+        Ok(results)
+    }
+
+    /// Search content by tag query (or the whole catalog without one) filtered by
+    /// difficulty, author and creation date, sorted and returned one page at a time
+    pub fn search_content_filtered(
+        env: Env,
+        query: Option<TagQuery>,
+        filter: ContentFilter,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<ContentPage, Error> {
+        // Verify contract is initialized
+        if !env.storage().instance().has(&INITIALIZED_KEY) {
+            return Err(Error::NotInitialized);
+        }
+
+        // Validate the query and filter
+        if let Some(query) = query.as_ref() {
+            if !crate::validate::validate_tag_query(query) {
+                return Err(Error::InvalidInput);
+            }
+        }
+        if !filter.is_valid() || limit == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let page = crate::search::search_content_filtered(&env, &query, &filter, cursor, limit);
 
         // Emit search performed event
         Events::search_performed(
             &env,
-            &String::from_str(&env, "multi-tag"),
-            results.len() as u32,
+            &String::from_str(&env, "filtered"),
+            page.contents.len(),
         );
 
+        Ok(page)
+    }
+
+    /// Search titles and descriptions by keywords, ranked by the number of query tokens matched
+    pub fn search_keywords(env: Env, query: String) -> Result<Vec<Content>, Error> {
+        // Verify contract is initialized
+        if !env.storage().instance().has(&INITIALIZED_KEY) {
+            return Err(Error::NotInitialized);
+        }
+
+        // Validate the query
+        if !crate::validate::validate_subject(&query) {
+            return Err(Error::InvalidInput);
+        }
+
+        let results = crate::search::keywords::search_keywords(&env, &query)?;
+
+        // Emit search performed event
+        Events::search_performed(&env, &query, results.len());
+
         Ok(results)
     }
 
@@ -26,3 +26,13 @@ pub struct Content {
 pub struct ContentList {
     pub contents: Vec<Content>,
 }
+
+/// One page of filtered search results. Pass `next_cursor` back to get the next page
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentPage {
+    pub contents: Vec<Content>,
+    /// ID of the last content examined
+    pub next_cursor: u64,
+    pub has_more: bool,
+}
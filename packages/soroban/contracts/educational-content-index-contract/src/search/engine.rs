@@ -1,7 +1,14 @@
+use super::filters::{evaluate_tag_query, ContentFilter, SortOrder, TagQuery};
 use crate::error::Error;
-use crate::metadata::Content;
+use crate::metadata::{Content, ContentPage};
 use crate::storage::ContentStorage;
-use soroban_sdk::{Env, String as SorobanString, Vec};
+use soroban_sdk::{Env, Map, String as SorobanString, Vec};
+
+// Largest page a filtered search will return
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Most index entries a filtered search examines per call
+const MAX_SCAN: u32 = 200;
 
 /// Core search functionality for educational content with indexed optimization
 pub fn search_content(env: &Env, subject: SorobanString) -> Result<Vec<Content>, Error> {
@@ -23,33 +30,6 @@ pub fn search_content(env: &Env, subject: SorobanString) -> Result<Vec<Content>,
     Err(Error::NoMatchingContent)
 }
 
-/// Linear search implementation (original algorithm) for fallback
-fn search_content_linear(env: &Env, subject: SorobanString) -> Result<Vec<Content>, Error> {
-    let contents = ContentStorage::get_all_content(env);
-    let mut results = Vec::new(env);
-
-    for content in contents.iter() {
-        // Check if the content has the searched tag
-        let mut found = false;
-        for tag in content.subject_tags.iter() {
-            if tag == subject {
-                found = true;
-                break;
-            }
-        }
-
-        if found {
-            results.push_back(content.clone());
-        }
-    }
-
-    if results.is_empty() {
-        Err(Error::NoMatchingContent)
-    } else {
-        Ok(results)
-    }
-}
-
 /// Batch search for multiple tags using indexed search - efficient for complex queries
 pub fn search_content_multi_tag(
     env: &Env,
@@ -84,3 +64,111 @@ pub fn search_content_multi_tag(
         Ok(all_results)
     }
 }
+
+/// Boolean tag search (AND / OR / NOT) evaluated on the tag index
+pub fn search_content_query(env: &Env, query: &TagQuery) -> Result<Vec<Content>, Error> {
+    let mut results = Vec::new(env);
+
+    for content_id in evaluate_tag_query(env, query).iter() {
+        if let Some(content) = ContentStorage::get_content_by_id_indexed(env, content_id) {
+            results.push_back(content);
+        }
+    }
+
+    if results.is_empty() {
+        Err(Error::NoMatchingContent)
+    } else {
+        Ok(results)
+    }
+}
+
+/// Filtered, sorted and paginated search. Walks the date or title index (or
+/// content IDs when unsorted), keeping the content selected by the tag query
+/// that passes the filter. `cursor` is the ID of the last content examined, 0
+/// to start; the walk stops after MAX_SCAN entries so a page never exceeds
+/// resource limits, and callers continue from `next_cursor`
+pub fn search_content_filtered(
+    env: &Env,
+    query: &Option<TagQuery>,
+    filter: &ContentFilter,
+    cursor: u64,
+    limit: u32,
+) -> ContentPage {
+    // Content selected by the tag query, by ascending ID
+    let candidates = query.as_ref().map(|query| {
+        let mut candidates = Map::new(env);
+        for content_id in evaluate_tag_query(env, query).iter() {
+            candidates.set(content_id, ());
+        }
+        candidates
+    });
+
+    // The order to walk; None walks every content ID in turn
+    let order = match filter.sort {
+        SortOrder::Unsorted => candidates.as_ref().map(|candidates| candidates.keys()),
+        SortOrder::NewestFirst | SortOrder::OldestFirst => {
+            Some(ContentStorage::get_content_ids_by_date(env))
+        }
+        SortOrder::TitleAscending | SortOrder::TitleDescending => {
+            Some(ContentStorage::get_content_ids_by_title(env))
+        }
+    };
+    let reversed = matches!(
+        filter.sort,
+        SortOrder::NewestFirst | SortOrder::TitleDescending
+    );
+    let total = match &order {
+        Some(content_ids) => content_ids.len(),
+        None => ContentStorage::get_last_content_id(env) as u32,
+    };
+
+    // Resume just after the cursor content
+    let mut position = match (&order, cursor) {
+        (_, 0) => 0,
+        (None, cursor) => (cursor as u32).min(total),
+        (Some(content_ids), cursor) if filter.sort == SortOrder::Unsorted => {
+            match content_ids.binary_search(cursor) {
+                Ok(index) => index + 1,
+                Err(index) => index,
+            }
+        }
+        (Some(content_ids), cursor) => match content_ids.first_index_of(cursor) {
+            Some(index) if reversed => total - index,
+            Some(index) => index + 1,
+            None => total,
+        },
+    };
+
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let mut contents = Vec::new(env);
+    let mut next_cursor = cursor;
+    let mut scanned = 0;
+    while position < total && contents.len() < limit && scanned < MAX_SCAN {
+        let content_id = match &order {
+            Some(content_ids) if reversed => content_ids.get_unchecked(total - 1 - position),
+            Some(content_ids) => content_ids.get_unchecked(position),
+            None => position as u64 + 1,
+        };
+        position += 1;
+        scanned += 1;
+        next_cursor = content_id;
+
+        if candidates
+            .as_ref()
+            .is_some_and(|candidates| !candidates.contains_key(content_id))
+        {
+            continue;
+        }
+        if let Some(content) = ContentStorage::get_content_by_id_indexed(env, content_id) {
+            if filter.matches(&content) {
+                contents.push_back(content);
+            }
+        }
+    }
+
+    ContentPage {
+        contents,
+        next_cursor,
+        has_more: position < total,
+    }
+}
@@ -1 +1,156 @@
+use crate::metadata::Content;
+use crate::storage::ContentStorage;
+use soroban_sdk::{contracttype, Env, String as SorobanString, Vec};
 
+/// Boolean query over subject tags. Content matches when it carries every
+/// `all_of` tag, at least one `any_of` tag (if any are given) and none of the
+/// `none_of` tags
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TagQuery {
+    pub all_of: Vec<SorobanString>,
+    pub any_of: Vec<SorobanString>,
+    pub none_of: Vec<SorobanString>,
+}
+
+impl TagQuery {
+    /// Number of tags across all three sets
+    pub fn tag_count(&self) -> u32 {
+        self.all_of.len() + self.any_of.len() + self.none_of.len()
+    }
+
+    /// Whether the query selects anything on its own; a query with only
+    /// `none_of` tags would have to scan the whole catalog
+    pub fn has_positive_terms(&self) -> bool {
+        !self.all_of.is_empty() || !self.any_of.is_empty()
+    }
+}
+
+/// Order of filtered search results. Ascending orders break ties by ascending
+/// content ID; descending ones walk them backwards
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortOrder {
+    /// Ascending content ID, which is the order content was added
+    Unsorted,
+    NewestFirst,
+    OldestFirst,
+    TitleAscending,
+    TitleDescending,
+}
+
+/// Attribute filters applied on top of a tag query. Unset fields match everything
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContentFilter {
+    pub difficulty_level: Option<SorobanString>,
+    pub author: Option<SorobanString>,
+    /// Earliest creation date, inclusive
+    pub created_from: Option<u64>,
+    /// Latest creation date, inclusive
+    pub created_to: Option<u64>,
+    pub sort: SortOrder,
+}
+
+impl ContentFilter {
+    /// Whether the creation-date range is well formed
+    pub fn is_valid(&self) -> bool {
+        match (self.created_from, self.created_to) {
+            (Some(from), Some(to)) => from <= to,
+            _ => true,
+        }
+    }
+
+    /// Whether content passes every attribute filter. Content without a
+    /// creation date never matches a date range
+    pub fn matches(&self, content: &Content) -> bool {
+        if self.difficulty_level.is_some() && content.difficulty_level != self.difficulty_level {
+            return false;
+        }
+        if self.author.is_some() && content.author != self.author {
+            return false;
+        }
+        if self.created_from.is_some() || self.created_to.is_some() {
+            let Some(date) = content.creation_date else {
+                return false;
+            };
+            if self.created_from.is_some_and(|from| date < from)
+                || self.created_to.is_some_and(|to| date > to)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Evaluate a tag query against the tag index, returning matching content IDs
+/// in the order of the first tag's index entry
+pub fn evaluate_tag_query(env: &Env, query: &TagQuery) -> Vec<u64> {
+    // AND: intersect the ID lists of every required tag
+    let mut matches: Option<Vec<u64>> = None;
+    for tag in query.all_of.iter() {
+        let ids = ContentStorage::get_content_ids_by_tag(env, &tag);
+        matches = Some(match matches {
+            Some(current) => intersect(env, &current, &ids),
+            None => ids,
+        });
+    }
+
+    // OR: union the ID lists of the optional tags, then narrow the AND result
+    if !query.any_of.is_empty() {
+        let mut any = Vec::new(env);
+        for tag in query.any_of.iter() {
+            any = union(&any, &ContentStorage::get_content_ids_by_tag(env, &tag));
+        }
+        matches = Some(match matches {
+            Some(current) => intersect(env, &current, &any),
+            None => any,
+        });
+    }
+
+    // NOT: drop anything carrying an excluded tag
+    let mut matches = matches.unwrap_or_else(|| Vec::new(env));
+    for tag in query.none_of.iter() {
+        matches = difference(
+            env,
+            &matches,
+            &ContentStorage::get_content_ids_by_tag(env, &tag),
+        );
+    }
+
+    matches
+}
+
+/// IDs present in both lists, in the order of `left`
+pub fn intersect(env: &Env, left: &Vec<u64>, right: &Vec<u64>) -> Vec<u64> {
+    let mut result = Vec::new(env);
+    for id in left.iter() {
+        if right.contains(id) {
+            result.push_back(id);
+        }
+    }
+    result
+}
+
+/// IDs of `left` followed by those of `right` not already present
+pub fn union(left: &Vec<u64>, right: &Vec<u64>) -> Vec<u64> {
+    let mut result = left.clone();
+    for id in right.iter() {
+        if !result.contains(id) {
+            result.push_back(id);
+        }
+    }
+    result
+}
+
+/// IDs of `left` that are not in `right`
+pub fn difference(env: &Env, left: &Vec<u64>, right: &Vec<u64>) -> Vec<u64> {
+    let mut result = Vec::new(env);
+    for id in left.iter() {
+        if !right.contains(id) {
+            result.push_back(id);
+        }
+    }
+    result
+}
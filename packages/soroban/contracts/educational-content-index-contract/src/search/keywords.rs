@@ -0,0 +1,92 @@
+use crate::error::Error;
+use crate::metadata::Content;
+use crate::storage::ContentStorage;
+use soroban_sdk::{Bytes, Env, Map, String as SorobanString, Vec};
+
+// Longest text that gets tokenized (the description limit)
+const MAX_TEXT_LEN: usize = 1000;
+
+// Common words that carry no meaning on their own
+const STOP_WORDS: [&[u8]; 24] = [
+    b"a", b"an", b"and", b"are", b"as", b"at", b"be", b"by", b"for", b"from", b"how", b"in",
+    b"into", b"is", b"it", b"of", b"on", b"or", b"that", b"the", b"this", b"to", b"what", b"with",
+];
+
+/// Split text into distinct lowercase tokens on non-alphanumeric characters,
+/// dropping stop-words. Bytes outside ASCII are kept so accented words stay whole
+pub fn tokenize(env: &Env, text: &SorobanString) -> Vec<Bytes> {
+    let mut buffer = [0u8; MAX_TEXT_LEN];
+    let len = (text.len() as usize).min(MAX_TEXT_LEN);
+    text.copy_into_slice(&mut buffer[..len]);
+    let text = &mut buffer[..len];
+    text.make_ascii_lowercase();
+
+    let mut tokens = Vec::new(env);
+    for word in text.split(|byte| byte.is_ascii() && !byte.is_ascii_alphanumeric()) {
+        if word.is_empty() || STOP_WORDS.contains(&word) {
+            continue;
+        }
+        let token = Bytes::from_slice(env, word);
+        if !tokens.contains(&token) {
+            tokens.push_back(token);
+        }
+    }
+    tokens
+}
+
+/// Distinct tokens of a content's title and description
+pub fn content_tokens(env: &Env, content: &Content) -> Vec<Bytes> {
+    let mut tokens = tokenize(env, &content.title);
+    for token in tokenize(env, &content.description).iter() {
+        if !tokens.contains(&token) {
+            tokens.push_back(token);
+        }
+    }
+    tokens
+}
+
+/// Keyword search over titles and descriptions, ranked by the number of query
+/// tokens each content matches. Ties keep the order content was first matched
+pub fn search_keywords(env: &Env, query: &SorobanString) -> Result<Vec<Content>, Error> {
+    let tokens = tokenize(env, query);
+    if tokens.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+
+    // Count matched tokens per content through the inverted index
+    let mut counts: Map<u64, u32> = Map::new(env);
+    let mut order = Vec::new(env);
+    for token in tokens.iter() {
+        for content_id in ContentStorage::get_content_ids_by_token(env, &token).iter() {
+            let count = counts.get(content_id).unwrap_or(0);
+            if count == 0 {
+                order.push_back(content_id);
+            }
+            counts.set(content_id, count + 1);
+        }
+    }
+
+    // Stable insertion sort by descending match count
+    let mut ranked: Vec<u64> = Vec::new(env);
+    for content_id in order.iter() {
+        let count = counts.get_unchecked(content_id);
+        let mut position = ranked.len();
+        while position > 0 && counts.get_unchecked(ranked.get_unchecked(position - 1)) < count {
+            position -= 1;
+        }
+        ranked.insert(position, content_id);
+    }
+
+    let mut results = Vec::new(env);
+    for content_id in ranked.iter() {
+        if let Some(content) = ContentStorage::get_content_by_id_indexed(env, content_id) {
+            results.push_back(content);
+        }
+    }
+
+    if results.is_empty() {
+        Err(Error::NoMatchingContent)
+    } else {
+        Ok(results)
+    }
+}
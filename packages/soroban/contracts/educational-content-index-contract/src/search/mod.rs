@@ -1,5 +1,9 @@
 pub mod engine;
 pub mod filters;
+pub mod keywords;
 
 // Re-export the main search functions for backward compatibility and new features
-pub use engine::{search_content, search_content_multi_tag};
+pub use engine::{
+    search_content, search_content_filtered, search_content_multi_tag, search_content_query,
+};
+pub use filters::{ContentFilter, TagQuery};
@@ -1,9 +1,56 @@
 use crate::metadata::{Content, ContentList};
-use soroban_sdk::{symbol_short, Env, String as SorobanString, Symbol, Vec};
+use crate::search::keywords::content_tokens;
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Bytes, BytesN, Env, String as SorobanString, Symbol,
+    Vec,
+};
 
 const CONTENT_KEY: Symbol = symbol_short!("CONTENT");
 const NEXT_ID_KEY: Symbol = symbol_short!("NEXT_ID");
 
+/// Keys of the search indices, derived from the full content ID, tag or token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexKey {
+    /// Content stored by its ID
+    Content(u64),
+    /// IDs of the content carrying a tag, keyed by the SHA-256 of the tag
+    Tag(BytesN<32>),
+    /// IDs of the content whose title or description contains a keyword token,
+    /// keyed by the SHA-256 of the token
+    Token(BytesN<32>),
+    /// IDs of all content by creation date, oldest first (undated first), then ID
+    ByDate,
+    /// IDs of all content by title, then ID
+    ByTitle,
+}
+
+/// Symbol keys used by earlier versions, which bucketed content IDs above 9
+/// and tags of the same length together. Removed by `rebuild_indices`
+const LEGACY_INDEX_KEYS: [Symbol; 21] = [
+    symbol_short!("CNT_0"),
+    symbol_short!("CNT_1"),
+    symbol_short!("CNT_2"),
+    symbol_short!("CNT_3"),
+    symbol_short!("CNT_4"),
+    symbol_short!("CNT_5"),
+    symbol_short!("CNT_6"),
+    symbol_short!("CNT_7"),
+    symbol_short!("CNT_8"),
+    symbol_short!("CNT_9"),
+    symbol_short!("CNT_BIG"),
+    symbol_short!("TAG70"),
+    symbol_short!("TAG77"),
+    symbol_short!("TAG7X"),
+    symbol_short!("TAG_10"),
+    symbol_short!("TAG_11"),
+    symbol_short!("TAG_6"),
+    symbol_short!("TAG_4"),
+    symbol_short!("TAG_8"),
+    symbol_short!("TAG_3"),
+    symbol_short!("TAG_OTH"),
+];
+
 pub struct ContentStorage;
 
 impl ContentStorage {
@@ -16,8 +63,10 @@ impl ContentStorage {
         // Get existing content to update tag indices
         let old_content = Self::get_content_by_id(env, content.id);
 
-        // Update tag indices
+        // Update tag, keyword and sort indices
         Self::update_tag_indices(env, &old_content, Some(content));
+        Self::update_token_indices(env, &old_content, Some(content));
+        Self::update_sort_indices(env, content);
 
         // Maintain backward compatibility with existing list structure
         let mut content_list = if storage.has(&CONTENT_KEY) {
@@ -116,26 +165,90 @@ impl ContentStorage {
         // Remove old tag mappings if content existed before
         if let Some(old) = old_content {
             for tag in old.subject_tags.iter() {
-                Self::remove_content_from_tag_index(env, &tag, old.id);
+                Self::remove_content_from_index(env, &Self::tag_index_key(env, &tag), old.id);
             }
         }
 
         // Add new tag mappings if content is being added/updated
         if let Some(new) = new_content {
             for tag in new.subject_tags.iter() {
-                Self::add_content_to_tag_index(env, &tag, new.id);
+                Self::add_content_to_index(env, &Self::tag_index_key(env, &tag), new.id);
             }
         }
 
         storage.extend_ttl(50, 100);
     }
 
-    /// Add content ID to a tag's index
-    fn add_content_to_tag_index(env: &Env, tag: &SorobanString, content_id: u64) {
+    /// Update keyword token indices when content is added/updated/removed
+    fn update_token_indices(
+        env: &Env,
+        old_content: &Option<Content>,
+        new_content: Option<&Content>,
+    ) {
         let storage = env.storage().instance();
-        let tag_key = Self::tag_index_key(tag);
 
-        let mut content_ids: Vec<u64> = storage.get(&tag_key).unwrap_or_else(|| Vec::new(env));
+        if let Some(old) = old_content {
+            for token in content_tokens(env, old).iter() {
+                Self::remove_content_from_index(env, &Self::token_index_key(env, &token), old.id);
+            }
+        }
+
+        if let Some(new) = new_content {
+            for token in content_tokens(env, new).iter() {
+                Self::add_content_to_index(env, &Self::token_index_key(env, &token), new.id);
+            }
+        }
+
+        storage.extend_ttl(50, 100);
+    }
+
+    /// Move content to its place in the date and title orderings
+    fn update_sort_indices(env: &Env, content: &Content) {
+        let storage = env.storage().instance();
+
+        for index_key in [IndexKey::ByDate, IndexKey::ByTitle] {
+            let mut content_ids: Vec<u64> =
+                storage.get(&index_key).unwrap_or_else(|| Vec::new(env));
+            if let Some(position) = content_ids.first_index_of(content.id) {
+                content_ids.remove(position);
+            }
+
+            // Binary search for the first entry that sorts after the content
+            let (mut low, mut high) = (0, content_ids.len());
+            while low < high {
+                let middle = (low + high) / 2;
+                let sorts_before =
+                    Self::get_content_by_id_indexed(env, content_ids.get_unchecked(middle))
+                        .is_some_and(|other| Self::sorts_before(&index_key, &other, content));
+                if sorts_before {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+
+            content_ids.insert(low, content.id);
+            storage.set(&index_key, &content_ids);
+        }
+    }
+
+    /// Whether `left` goes before `right` in a date or title ordering; ties by ID
+    fn sorts_before(index_key: &IndexKey, left: &Content, right: &Content) -> bool {
+        let ordering = match index_key {
+            IndexKey::ByTitle => left.title.cmp(&right.title),
+            _ => left
+                .creation_date
+                .unwrap_or(0)
+                .cmp(&right.creation_date.unwrap_or(0)),
+        };
+        ordering.then(left.id.cmp(&right.id)).is_lt()
+    }
+
+    /// Add content ID to a tag or token index
+    fn add_content_to_index(env: &Env, index_key: &IndexKey, content_id: u64) {
+        let storage = env.storage().instance();
+
+        let mut content_ids: Vec<u64> = storage.get(index_key).unwrap_or_else(|| Vec::new(env));
 
         // Check if content_id already exists to avoid duplicates
         let mut already_exists = false;
@@ -148,16 +261,15 @@ impl ContentStorage {
 
         if !already_exists {
             content_ids.push_back(content_id);
-            storage.set(&tag_key, &content_ids);
+            storage.set(index_key, &content_ids);
         }
     }
 
-    /// Remove content ID from a tag's index
-    fn remove_content_from_tag_index(env: &Env, tag: &SorobanString, content_id: u64) {
+    /// Remove content ID from a tag or token index
+    fn remove_content_from_index(env: &Env, index_key: &IndexKey, content_id: u64) {
         let storage = env.storage().instance();
-        let tag_key = Self::tag_index_key(tag);
 
-        if let Some(content_ids) = storage.get::<Symbol, Vec<u64>>(&tag_key) {
+        if let Some(content_ids) = storage.get::<IndexKey, Vec<u64>>(index_key) {
             let mut new_ids = Vec::new(env);
 
             for existing_id in content_ids.iter() {
@@ -167,10 +279,10 @@ impl ContentStorage {
             }
 
             if new_ids.is_empty() {
-                // Remove the tag index entirely if no content IDs remain
-                storage.remove(&tag_key);
+                // Remove the index entry entirely if no content IDs remain
+                storage.remove(index_key);
             } else {
-                storage.set(&tag_key, &new_ids);
+                storage.set(index_key, &new_ids);
             }
         }
     }
@@ -178,10 +290,40 @@ impl ContentStorage {
     /// Get content IDs for a specific tag using index
     pub fn get_content_ids_by_tag(env: &Env, tag: &SorobanString) -> Vec<u64> {
         let storage = env.storage().instance();
-        let tag_key = Self::tag_index_key(tag);
+        let tag_key = Self::tag_index_key(env, tag);
         storage.get(&tag_key).unwrap_or_else(|| Vec::new(env))
     }
 
+    /// Get content IDs whose title or description contains a keyword token
+    pub fn get_content_ids_by_token(env: &Env, token: &Bytes) -> Vec<u64> {
+        let storage = env.storage().instance();
+        storage
+            .get(&Self::token_index_key(env, token))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// IDs of all content by creation date, oldest first
+    pub fn get_content_ids_by_date(env: &Env) -> Vec<u64> {
+        let storage = env.storage().instance();
+        storage
+            .get(&IndexKey::ByDate)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// IDs of all content by title
+    pub fn get_content_ids_by_title(env: &Env) -> Vec<u64> {
+        let storage = env.storage().instance();
+        storage
+            .get(&IndexKey::ByTitle)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Highest content ID assigned so far; IDs run from 1 up to it
+    pub fn get_last_content_id(env: &Env) -> u64 {
+        let storage = env.storage().instance();
+        storage.get(&NEXT_ID_KEY).unwrap_or(0)
+    }
+
     /// Search content by tag using indexed lookup - O(1) + O(m) where m is matching items
     pub fn search_content_by_tag_indexed(env: &Env, tag: &SorobanString) -> Vec<Content> {
         let content_ids = Self::get_content_ids_by_tag(env, tag);
@@ -199,80 +341,54 @@ impl ContentStorage {
     // ========== Helper Functions for Key Generation ==========
 
     /// Generate storage key for content by ID
-    fn content_id_key(content_id: u64) -> Symbol {
-        // Convert content_id to symbol for storage key
-        // Using a simple approach that works within Soroban's constraints
-        match content_id {
-            0 => symbol_short!("CNT_0"),
-            1 => symbol_short!("CNT_1"),
-            2 => symbol_short!("CNT_2"),
-            3 => symbol_short!("CNT_3"),
-            4 => symbol_short!("CNT_4"),
-            5 => symbol_short!("CNT_5"),
-            6 => symbol_short!("CNT_6"),
-            7 => symbol_short!("CNT_7"),
-            8 => symbol_short!("CNT_8"),
-            9 => symbol_short!("CNT_9"),
-            _ => {
-                // For IDs > 9, we'll use a different approach
-                // This is a simplified version - in production you might want to use different storage patterns
-                symbol_short!("CNT_BIG")
-            }
-        }
+    fn content_id_key(content_id: u64) -> IndexKey {
+        IndexKey::Content(content_id)
     }
 
-    /// Generate storage key for tag index
-    fn tag_index_key(tag: &SorobanString) -> Symbol {
-        // Create a simple but effective tag-specific key
-        // This approach uses tag length plus a simple content differentiation
-        // In production, you'd want a proper hash function
-
-        let len = tag.len();
-
-        // For common lengths, create sub-buckets based on content
-        match len {
-            7 => {
-                // Length 7 tags get different buckets to avoid collision
-                // We'll use a simple pattern: every tag gets a unique bucket
-                // based on a simple hash-like calculation
-                let simple_hash = len * 10 + (len % 7);
-                match simple_hash {
-                    70 => symbol_short!("TAG70"), // Most 7-length tags
-                    77 => symbol_short!("TAG77"), // Some 7-length tags
-                    _ => symbol_short!("TAG7X"),  // Other 7-length tags
-                }
-            }
-            10 => symbol_short!("TAG_10"), // "blockchain", "technology"
-            11 => symbol_short!("TAG_11"), // "programming"
-            6 => symbol_short!("TAG_6"),   // "crypto"
-            4 => symbol_short!("TAG_4"),   // "math"
-            8 => symbol_short!("TAG_8"),   // "language"
-            3 => symbol_short!("TAG_3"),   // "art"
-            _ => symbol_short!("TAG_OTH"), // Other lengths
-        }
+    /// Generate storage key for tag index from a hash of the whole tag
+    fn tag_index_key(env: &Env, tag: &SorobanString) -> IndexKey {
+        IndexKey::Tag(env.crypto().sha256(&tag.clone().to_xdr(env)).into())
     }
 
-    /// Rebuild tag indices for all existing content - useful for migration
+    /// Generate storage key for a keyword token index from a hash of the token
+    fn token_index_key(env: &Env, token: &Bytes) -> IndexKey {
+        IndexKey::Token(env.crypto().sha256(token).into())
+    }
+
+    /// Rebuild tag and keyword indices for all existing content - useful for migration
     pub fn rebuild_indices(env: &Env) {
         let storage = env.storage().instance();
 
         let all_content = Self::get_all_content(env);
 
+        // Drop the colliding keys written by earlier versions
+        for key in LEGACY_INDEX_KEYS.iter() {
+            storage.remove(key);
+        }
+
         // First, clear all existing indices by removing them
         // We'll rebuild them cleanly from scratch
         for content in all_content.iter() {
             for tag in content.subject_tags.iter() {
-                let tag_key = Self::tag_index_key(&tag);
+                let tag_key = Self::tag_index_key(env, &tag);
                 storage.remove(&tag_key);
             }
+            for token in content_tokens(env, &content).iter() {
+                storage.remove(&Self::token_index_key(env, &token));
+            }
         }
 
+        storage.remove(&IndexKey::ByDate);
+        storage.remove(&IndexKey::ByTitle);
+
         // Now rebuild indices for all content
         for content in all_content.iter() {
             // Add content to individual storage
             Self::set_content_by_id(env, &content);
             // Update the indices for this content (starting from clean slate)
             Self::update_tag_indices(env, &None, Some(&content));
+            Self::update_token_indices(env, &None, Some(&content));
+            Self::update_sort_indices(env, &content);
         }
 
         storage.extend_ttl(50, 100);
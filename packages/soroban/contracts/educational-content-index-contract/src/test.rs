@@ -3,6 +3,18 @@ use soroban_sdk::{
     testutils::Events, Address, Env, String as SorobanString, Symbol, TryIntoVal, Vec,
 };
 
+// Data of a content update event: title, description, tags, url, author,
+// difficulty and creation date
+type UpdatedEventData = (
+    SorobanString,
+    SorobanString,
+    Vec<SorobanString>,
+    SorobanString,
+    Option<SorobanString>,
+    Option<SorobanString>,
+    Option<u64>,
+);
+
 fn setup_contract(env: &Env) -> Address {
     let contract_id = env.register(ContentSearchContract, ());
     env.as_contract(&contract_id, || {
@@ -336,15 +348,7 @@ fn test_update_content_emits_event() {
         _author,
         _difficulty,
         _creation_date,
-    ): (
-        SorobanString,
-        SorobanString,
-        Vec<SorobanString>,
-        SorobanString,
-        Option<SorobanString>,
-        Option<SorobanString>,
-        Option<u64>,
-    ) = data.try_into_val(&env).unwrap();
+    ): UpdatedEventData = data.try_into_val(&env).unwrap();
 
     assert_eq!(updated_title, new_title);
     assert_eq!(updated_description, new_description);
@@ -839,3 +843,468 @@ fn test_search_performance_with_larger_dataset() {
     // Should find items 1, 4, 7 (every 3rd item starting from 1)
     assert_eq!(programming_results.len(), 3);
 }
+
+// ========== Index Key Tests ==========
+
+fn add_tagged_content(env: &Env, contract_id: &Address, title: &str, tags: &[&str]) -> u64 {
+    let mut subject_tags = Vec::new(env);
+    for tag in tags {
+        subject_tags.push_back(SorobanString::from_str(env, tag));
+    }
+    env.as_contract(contract_id, || {
+        ContentSearchContract::add_content(
+            env.clone(),
+            SorobanString::from_str(env, title),
+            SorobanString::from_str(env, "Description"),
+            subject_tags,
+            SorobanString::from_str(env, "https://example.com"),
+            None,
+            None,
+            None,
+        )
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_index_keys_do_not_collide() {
+    let env = Env::default();
+    let contract_id = setup_contract(&env);
+
+    // Tags of the same length get separate index entries
+    let physics_id = add_tagged_content(&env, &contract_id, "Physics", &["physics"]);
+    let history_id = add_tagged_content(&env, &contract_id, "History", &["history"]);
+
+    let physics = env
+        .as_contract(&contract_id, || {
+            ContentSearchContract::search_content(
+                env.clone(),
+                SorobanString::from_str(&env, "physics"),
+            )
+        })
+        .unwrap();
+    assert_eq!(physics.len(), 1);
+    assert_eq!(physics.get_unchecked(0).id, physics_id);
+
+    let history = env
+        .as_contract(&contract_id, || {
+            ContentSearchContract::search_content(
+                env.clone(),
+                SorobanString::from_str(&env, "history"),
+            )
+        })
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get_unchecked(0).id, history_id);
+
+    // Content with IDs above 9 is stored under its own key
+    for _ in 0..10 {
+        add_tagged_content(&env, &contract_id, "Filler", &["filler"]);
+    }
+    let first_late = add_tagged_content(&env, &contract_id, "Late 1", &["late"]);
+    let second_late = add_tagged_content(&env, &contract_id, "Late 2", &["late"]);
+    assert!(first_late > 9);
+
+    env.as_contract(&contract_id, || {
+        let first =
+            crate::storage::ContentStorage::get_content_by_id_indexed(&env, first_late).unwrap();
+        let second =
+            crate::storage::ContentStorage::get_content_by_id_indexed(&env, second_late).unwrap();
+        assert_eq!(first.title, SorobanString::from_str(&env, "Late 1"));
+        assert_eq!(second.title, SorobanString::from_str(&env, "Late 2"));
+    });
+
+    let late = env
+        .as_contract(&contract_id, || {
+            ContentSearchContract::search_content(
+                env.clone(),
+                SorobanString::from_str(&env, "late"),
+            )
+        })
+        .unwrap();
+    assert_eq!(late.len(), 2);
+}
+
+#[test]
+fn test_rebuild_removes_legacy_keys() {
+    let env = Env::default();
+    let contract_id = setup_contract(&env);
+
+    let id = add_tagged_content(&env, &contract_id, "Physics", &["physics"]);
+
+    // Simulate entries written by the old bucketed keys
+    let legacy_tag = soroban_sdk::symbol_short!("TAG70");
+    let legacy_content = soroban_sdk::symbol_short!("CNT_BIG");
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&legacy_tag, &Vec::from_array(&env, [id, 99u64]));
+        storage.set(&legacy_content, &99u64);
+    });
+
+    env.as_contract(&contract_id, || {
+        ContentSearchContract::rebuild_search_indices(env.clone())
+    })
+    .unwrap();
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        assert!(!storage.has(&legacy_tag));
+        assert!(!storage.has(&legacy_content));
+    });
+
+    let results = env
+        .as_contract(&contract_id, || {
+            ContentSearchContract::search_content(
+                env.clone(),
+                SorobanString::from_str(&env, "physics"),
+            )
+        })
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get_unchecked(0).id, id);
+}
+
+// ========== Boolean Query Tests ==========
+
+fn tags(env: &Env, tags: &[&str]) -> Vec<SorobanString> {
+    let mut result = Vec::new(env);
+    for tag in tags {
+        result.push_back(SorobanString::from_str(env, tag));
+    }
+    result
+}
+
+#[test]
+fn test_boolean_tag_query() {
+    let env = Env::default();
+    let contract_id = setup_contract(&env);
+
+    let intro = add_tagged_content(
+        &env,
+        &contract_id,
+        "Intro to Algebra",
+        &["algebra", "beginner-friendly"],
+    );
+    let _calculus = add_tagged_content(
+        &env,
+        &contract_id,
+        "Algebra for Calculus",
+        &["algebra", "beginner-friendly", "calculus"],
+    );
+    let advanced = add_tagged_content(&env, &contract_id, "Abstract Algebra", &["algebra"]);
+    let geometry = add_tagged_content(
+        &env,
+        &contract_id,
+        "Shapes",
+        &["geometry", "beginner-friendly"],
+    );
+
+    let search = |query: crate::search::TagQuery| {
+        env.as_contract(&contract_id, || {
+            ContentSearchContract::search_content_query(env.clone(), query)
+        })
+    };
+    let ids = |results: Vec<crate::metadata::Content>| {
+        let mut ids = Vec::new(&env);
+        for content in results.iter() {
+            ids.push_back(content.id);
+        }
+        ids
+    };
+
+    // algebra AND beginner-friendly NOT calculus
+    let results = search(crate::search::TagQuery {
+        all_of: tags(&env, &["algebra", "beginner-friendly"]),
+        any_of: tags(&env, &[]),
+        none_of: tags(&env, &["calculus"]),
+    })
+    .unwrap();
+    assert_eq!(ids(results), Vec::from_array(&env, [intro]));
+
+    // (algebra OR geometry) NOT beginner-friendly
+    let results = search(crate::search::TagQuery {
+        all_of: tags(&env, &[]),
+        any_of: tags(&env, &["algebra", "geometry"]),
+        none_of: tags(&env, &["beginner-friendly"]),
+    })
+    .unwrap();
+    assert_eq!(ids(results), Vec::from_array(&env, [advanced]));
+
+    // beginner-friendly AND (geometry OR calculus) NOT calculus
+    let results = search(crate::search::TagQuery {
+        all_of: tags(&env, &["beginner-friendly"]),
+        any_of: tags(&env, &["geometry", "calculus"]),
+        none_of: tags(&env, &["calculus"]),
+    })
+    .unwrap();
+    assert_eq!(ids(results), Vec::from_array(&env, [geometry]));
+
+    // Nothing carries both tags
+    let result = search(crate::search::TagQuery {
+        all_of: tags(&env, &["geometry", "calculus"]),
+        any_of: tags(&env, &[]),
+        none_of: tags(&env, &[]),
+    });
+    assert_eq!(result, Err(crate::error::Error::NoMatchingContent));
+
+    // A query needs at least one all_of or any_of tag
+    let result = search(crate::search::TagQuery {
+        all_of: tags(&env, &[]),
+        any_of: tags(&env, &[]),
+        none_of: tags(&env, &["calculus"]),
+    });
+    assert_eq!(result, Err(crate::error::Error::InvalidInput));
+}
+
+// ========== Filtered Search Tests ==========
+
+fn add_detailed_content(
+    env: &Env,
+    contract_id: &Address,
+    title: &str,
+    author: &str,
+    difficulty: &str,
+    creation_date: u64,
+) -> u64 {
+    env.as_contract(contract_id, || {
+        ContentSearchContract::add_content(
+            env.clone(),
+            SorobanString::from_str(env, title),
+            SorobanString::from_str(env, "Description"),
+            tags(env, &["math"]),
+            SorobanString::from_str(env, "https://example.com"),
+            Some(SorobanString::from_str(env, author)),
+            Some(SorobanString::from_str(env, difficulty)),
+            Some(creation_date),
+        )
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_filtered_search() {
+    use crate::search::filters::SortOrder;
+    use crate::search::ContentFilter;
+
+    let env = Env::default();
+    let contract_id = setup_contract(&env);
+
+    let geometry = add_detailed_content(&env, &contract_id, "Geometry", "ana", "Beginner", 300);
+    let algebra = add_detailed_content(&env, &contract_id, "Algebra", "ana", "Beginner", 100);
+    let calculus = add_detailed_content(&env, &contract_id, "Calculus", "ben", "Advanced", 200);
+    let topology = add_detailed_content(&env, &contract_id, "Topology", "ana", "Beginner", 400);
+    let undated = add_tagged_content(&env, &contract_id, "Undated", &["history"]);
+
+    let filter = |sort: SortOrder| ContentFilter {
+        difficulty_level: None,
+        author: None,
+        created_from: None,
+        created_to: None,
+        sort,
+    };
+    let search =
+        |query: Option<crate::search::TagQuery>, filter: ContentFilter, cursor: u64, limit: u32| {
+            env.as_contract(&contract_id, || {
+                ContentSearchContract::search_content_filtered(
+                    env.clone(),
+                    query,
+                    filter,
+                    cursor,
+                    limit,
+                )
+            })
+        };
+    let ids = |page: &crate::metadata::ContentPage| {
+        let mut ids = Vec::new(&env);
+        for content in page.contents.iter() {
+            ids.push_back(content.id);
+        }
+        ids
+    };
+
+    // Author and difficulty, newest first
+    let mut by_ana = filter(SortOrder::NewestFirst);
+    by_ana.author = Some(SorobanString::from_str(&env, "ana"));
+    by_ana.difficulty_level = Some(SorobanString::from_str(&env, "Beginner"));
+    let page = search(None, by_ana, 0, 10).unwrap();
+    assert_eq!(
+        ids(&page),
+        Vec::from_array(&env, [topology, geometry, algebra])
+    );
+    assert!(!page.has_more);
+
+    // Creation-date range (inclusive) leaves out undated content, title order
+    let mut in_range = filter(SortOrder::TitleAscending);
+    in_range.created_from = Some(100);
+    in_range.created_to = Some(300);
+    let page = search(None, in_range, 0, 10).unwrap();
+    assert_eq!(
+        ids(&page),
+        Vec::from_array(&env, [algebra, calculus, geometry])
+    );
+
+    // Combined with a tag query, oldest first, two per page
+    let math = crate::search::TagQuery {
+        all_of: tags(&env, &["math"]),
+        any_of: tags(&env, &[]),
+        none_of: tags(&env, &[]),
+    };
+    let first = search(Some(math.clone()), filter(SortOrder::OldestFirst), 0, 2).unwrap();
+    assert_eq!(ids(&first), Vec::from_array(&env, [algebra, calculus]));
+    assert!(first.has_more);
+    assert_eq!(first.next_cursor, calculus);
+
+    // The cursor resumes after the last content seen, even when content
+    // added in between sorts before it
+    let early = add_detailed_content(&env, &contract_id, "Arithmetic", "ben", "Beginner", 50);
+    let second = search(
+        Some(math),
+        filter(SortOrder::OldestFirst),
+        first.next_cursor,
+        2,
+    )
+    .unwrap();
+    assert_eq!(ids(&second), Vec::from_array(&env, [geometry, topology]));
+    assert!(!second.has_more);
+
+    // Whole catalog, title descending
+    let page = search(None, filter(SortOrder::TitleDescending), 0, 10).unwrap();
+    assert_eq!(page.contents.len(), 6);
+    assert_eq!(
+        page.contents.get_unchecked(0).title,
+        SorobanString::from_str(&env, "Undated")
+    );
+
+    // Unsorted pages follow content IDs
+    let first = search(None, filter(SortOrder::Unsorted), 0, 3).unwrap();
+    assert_eq!(
+        ids(&first),
+        Vec::from_array(&env, [geometry, algebra, calculus])
+    );
+    let second = search(None, filter(SortOrder::Unsorted), first.next_cursor, 3).unwrap();
+    assert_eq!(
+        ids(&second),
+        Vec::from_array(&env, [topology, undated, early])
+    );
+    assert!(!second.has_more);
+
+    // Inverted date range and empty pages are rejected
+    let mut inverted = filter(SortOrder::Unsorted);
+    inverted.created_from = Some(300);
+    inverted.created_to = Some(100);
+    assert_eq!(
+        search(None, inverted, 0, 10),
+        Err(crate::error::Error::InvalidInput)
+    );
+    assert_eq!(
+        search(None, filter(SortOrder::Unsorted), 0, 0),
+        Err(crate::error::Error::InvalidInput)
+    );
+}
+
+// ========== Keyword Search Tests ==========
+
+fn add_described_content(env: &Env, contract_id: &Address, title: &str, description: &str) -> u64 {
+    env.as_contract(contract_id, || {
+        ContentSearchContract::add_content(
+            env.clone(),
+            SorobanString::from_str(env, title),
+            SorobanString::from_str(env, description),
+            tags(env, &["general"]),
+            SorobanString::from_str(env, "https://example.com"),
+            None,
+            None,
+            None,
+        )
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_tokenize() {
+    let env = Env::default();
+    let tokens = crate::search::keywords::tokenize(
+        &env,
+        &SorobanString::from_str(&env, "The Rust-Programming guide, for RUST beginners!"),
+    );
+    let expected = Vec::from_array(
+        &env,
+        [
+            soroban_sdk::Bytes::from_slice(&env, b"rust"),
+            soroban_sdk::Bytes::from_slice(&env, b"programming"),
+            soroban_sdk::Bytes::from_slice(&env, b"guide"),
+            soroban_sdk::Bytes::from_slice(&env, b"beginners"),
+        ],
+    );
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn test_keyword_search() {
+    let env = Env::default();
+    let contract_id = setup_contract(&env);
+
+    let rust = add_described_content(
+        &env,
+        &contract_id,
+        "Rust Programming",
+        "Systems programming with ownership",
+    );
+    let soroban = add_described_content(
+        &env,
+        &contract_id,
+        "Smart contracts on Soroban",
+        "Write smart contracts in Rust",
+    );
+    let history = add_described_content(&env, &contract_id, "World History", "Ancient empires");
+
+    let search = |query: &str| {
+        env.as_contract(&contract_id, || {
+            ContentSearchContract::search_keywords(
+                env.clone(),
+                SorobanString::from_str(&env, query),
+            )
+        })
+    };
+
+    // Ranked by the number of query tokens matched in title or description
+    let results = search("Rust smart contracts").unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.get_unchecked(0).id, soroban);
+    assert_eq!(results.get_unchecked(1).id, rust);
+
+    let results = search("HISTORY of empires").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get_unchecked(0).id, history);
+
+    // Updating the text moves the content between tokens
+    env.as_contract(&contract_id, || {
+        ContentSearchContract::update_content(
+            env.clone(),
+            history,
+            SorobanString::from_str(&env, "Medieval Europe"),
+            SorobanString::from_str(&env, "Castles and kingdoms"),
+            tags(&env, &["general"]),
+            SorobanString::from_str(&env, "https://example.com"),
+            None,
+            None,
+            None,
+        )
+    })
+    .unwrap();
+    assert_eq!(
+        search("empires"),
+        Err(crate::error::Error::NoMatchingContent)
+    );
+    assert_eq!(search("castles").unwrap().get_unchecked(0).id, history);
+
+    // Keyword indices are rebuilt with the others
+    env.as_contract(&contract_id, || {
+        ContentSearchContract::rebuild_search_indices(env.clone())
+    })
+    .unwrap();
+    assert_eq!(search("ownership").unwrap().get_unchecked(0).id, rust);
+
+    // A query of only stop-words has nothing to search for
+    assert_eq!(search("the and of"), Err(crate::error::Error::InvalidInput));
+}
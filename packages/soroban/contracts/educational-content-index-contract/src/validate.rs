@@ -2,11 +2,28 @@ use soroban_sdk::{Env, String};
 
 use crate::error::Error;
 use crate::metadata::Content;
+use crate::search::TagQuery;
+
+// Most tags a single boolean query may name across its three sets
+const MAX_QUERY_TAGS: u32 = 20;
 
 pub fn validate_subject(subject: &String) -> bool {
     !subject.is_empty() && subject.len() <= 100
 }
 
+pub fn validate_tag_query(query: &TagQuery) -> bool {
+    if !query.has_positive_terms() || query.tag_count() > MAX_QUERY_TAGS {
+        return false;
+    }
+
+    query
+        .all_of
+        .iter()
+        .chain(query.any_of.iter())
+        .chain(query.none_of.iter())
+        .all(|tag| validate_subject(&tag))
+}
+
 pub fn validate_content(env: &Env, content: &Content) -> Result<(), Error> {
     // Validar título
     if content.title.is_empty() || content.title.len() > 200 {
@@ -1,3 +1,4 @@
+use super::models::{DutchSchedule, SealedBidConfig};
 use soroban_sdk::contracttype;
 
 /// Product condition rating
@@ -11,16 +12,30 @@ pub enum ProductCondition {
     Poor,
 }
 
+/// Auction pricing mechanism
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum AuctionType {
+    English,                 // Ascending bids, highest bidder wins at end_time
+    Dutch(DutchSchedule),    // Descending price, first buyer to accept wins
+    Sealed(SealedBidConfig), // Commit-reveal, winner pays the second-highest price
+    MultiUnit,               // Unit-price bids cleared at a uniform price across inventory
+    Reverse, // Service request: providers offer prices within the requester's budget
+}
+
 /// Auction status
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[contracttype]
 pub enum AuctionStatus {
-    Pending,   // Created but not yet started
-    Active,    // Auction is live and accepting bids
-    Ended,     // Auction time has expired
-    Cancelled, // Auction was cancelled
-    Completed, // Product has been delivered and verified
-    Disputed,  // There is an active dispute on this auction
+    Pending,       // Created but not yet started
+    Active,        // Auction is live and accepting bids
+    Revealing,     // Sealed-bid auction is waiting for bids to be revealed
+    Ended,         // Auction time has expired
+    ReserveNotMet, // Auction ended without a bid at or above the reserve price
+    Cancelled,     // Auction was cancelled
+    Expired,       // End time passed before the seller started the auction
+    Completed,     // Product has been delivered and verified
+    Disputed,      // There is an active dispute on this auction
 }
 
 /// Dispute status
@@ -29,8 +44,32 @@ pub enum AuctionStatus {
 pub enum DisputeStatus {
     None,
     Open,
+    Ruled,    // A resolver has ruled; final once the appeal window passes
+    Appealed, // A party appealed the ruling to a different resolver
     ResolvedForBuyer,
     ResolvedForSeller,
+    ResolvedSplit,
+}
+
+/// Outcome chosen by a resolver when settling a dispute
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum DisputeResolution {
+    RefundBuyer, // Full escrow goes back to the buyer
+    PaySeller,   // Full escrow goes to the seller
+    Split(u32),  // Percentage (0-100) refunded to the buyer, remainder to the seller
+}
+
+/// Why escrowed funds were paid out
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum SettlementKind {
+    Sale,             // Normal completion after delivery
+    DisputeRefund,    // Paid to the buyer by dispute resolution
+    DisputePayout,    // Paid to the seller by dispute resolution
+    ForfeitedDeposit, // Unrevealed sealed-bid deposit paid to the seller
+    PlatformFee,      // Commission deducted from a sale for the fee recipient
+    Shipping,         // Quoted shipping cost passed on to the seller
 }
 
 /// Shipping status
@@ -48,10 +87,30 @@ pub enum ShippingStatus {
 #[derive(Clone)]
 pub enum StorageKey {
     Admin,
-    Auctions,
+    PendingAdmin,
+    PaymentToken,
+    Auction(soroban_sdk::BytesN<32>),
     AuctionCounter,
+    AuctionIndex(u32),
+    BidPage(soroban_sdk::BytesN<32>, u32),
+    ProxyBid(soroban_sdk::BytesN<32>, soroban_sdk::Address),
     UserSelling(soroban_sdk::Address),
     UserBidding(soroban_sdk::Address),
+    UserBid(soroban_sdk::Address, soroban_sdk::BytesN<32>), // A bidder's latest bid on an auction
     Verifiers,
     Resolvers,
+    Carriers,
+    DisputeWindow,
+    FeeConfig,
+    FeeTotal(soroban_sdk::Address), // Fees collected per payment token
+    DisputeCase(soroban_sdk::BytesN<32>, soroban_sdk::Address), // Case file per auction and buyer
+    ResponseWindow,
+    AppealWindow,
+    ShippingZone(soroban_sdk::String), // Base shipping cost per zone code
+    ShippingRates,
+    SellerCarriers(soroban_sdk::Address),
+    ProductLineage(soroban_sdk::BytesN<32>), // Auctions listing a product, oldest first
+    AttestationQuorum,
+    Lot(soroban_sdk::BytesN<32>), // Products of a lot auction
+    SealedBid(soroban_sdk::BytesN<32>, soroban_sdk::Address), // A bidder's sealed commitment
 }
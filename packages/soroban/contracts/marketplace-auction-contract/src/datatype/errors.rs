@@ -0,0 +1,75 @@
+use soroban_sdk::contracterror;
+
+/// Errors returned by the auction contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    // General errors
+    AlreadyInitialized = 1, // Contract has already been initialized
+    NotInitialized = 2,     // Contract has not been initialized
+    Unauthorized = 3,       // Caller lacks the required role
+    AuctionNotFound = 4,    // No auction with this ID
+    NoPendingAdmin = 5,     // No admin transfer has been proposed
+    RoleNotAssigned = 6,    // Address does not hold the role being removed
+
+    // Auction creation errors
+    InvalidTimeRange = 10,       // End time is not after start time
+    InvalidInventory = 11,       // Inventory count is zero, or no such lot item
+    InvalidReservePrice = 12, // Reserve price is not positive, or starting price outside 0..=reserve
+    InvalidSoftClose = 13,    // Soft close settings are inconsistent or overflow the end time
+    InvalidDutchSchedule = 14, // Dutch price schedule is inconsistent
+    InvalidSealedConfig = 15, // Sealed-bid reveal duration or deposit is not positive
+    InvalidBidIncrement = 16, // Bid increment is negative or above 100%
+    InvalidBuyNow = 17,       // Buy now price or threshold is inconsistent
+    UnsupportedAuctionType = 18, // Option or operation not available for this auction type
+
+    // Lifecycle errors
+    AuctionNotPending = 20,   // Auction has already started or was cancelled
+    AuctionNotActive = 21,    // Auction is not accepting bids, or awaits product authentication
+    AuctionNotStarted = 22,   // Start time has not been reached
+    AuctionAlreadyEnded = 23, // End time has passed
+    AuctionNotEnded = 24,     // End time has not been reached, or the auction cannot be relisted
+    AuctionNotSold = 25,      // Auction has no winning bid, or is no longer awaiting delivery
+
+    // Bidding errors
+    InvalidAmount = 30,   // Amount is not positive, or an offer exceeds the budget
+    InvalidQuantity = 31, // Quantity is zero or exceeds inventory
+    BidTooLow = 32, // Bid is below the minimum bid, or an offer does not undercut the previous one
+    BuyNowUnavailable = 33, // Auction has no buy now price
+    BuyNowThresholdExceeded = 34, // Bidding has passed the buy now threshold
+    ProxyMaxTooLow = 35, // New proxy maximum does not exceed the current one
+
+    // Sealed-bid errors
+    AlreadyCommitted = 40,    // Bidder has already committed a bid
+    NoCommitment = 41,        // Bidder has no sealed bid or offer on this auction
+    AlreadyRevealed = 42,     // Bid has already been revealed
+    CommitmentMismatch = 43,  // Revealed values do not match the commitment
+    NotRevealing = 44,        // Auction is not in its reveal phase
+    RevealPhaseEnded = 45,    // Reveal window has closed
+    RevealPhaseNotEnded = 46, // Reveal window is still open
+
+    // Escrow errors
+    InsufficientEscrow = 50, // Payout exceeds the funds held for the auction, or an allocation was already paid out
+
+    // Shipping errors
+    NoShippingInfo = 60, // No shipping information, quote or rate for the zone and speed
+    InvalidShippingTransition = 61, // Shipping status cannot move backwards or repeat
+    NotDelivered = 62,   // Item has not been delivered yet
+    DisputeWindowOpen = 63, // Buyer can still dispute the delivery, or the ruling be appealed
+
+    // Dispute errors
+    NotHighestBidder = 70,      // Only the winning bidder can do this
+    DisputeNotAllowed = 71,     // Auction is not in a disputable state
+    DisputeAlreadyOpen = 72,    // A dispute has already been opened
+    NoOpenDispute = 73,         // There is no open dispute to resolve
+    InvalidSplit = 74,          // Split percentage exceeds 100
+    DisputeWindowClosed = 75,   // Dispute window after delivery, or the appeal window, has passed
+    SellerResponsePending = 76, // The seller has not answered the dispute
+    NoPendingRuling = 77,       // There is no ruling awaiting appeal
+    EvidenceLimitReached = 78,  // The party already filed the maximum evidence entries
+
+    // Fee and configuration errors
+    InvalidFeeConfig = 80, // Fee basis points above 100% or negative flat fee
+    InvalidConfig = 81,    // Shipping rates, quorum or another admin setting out of range
+}
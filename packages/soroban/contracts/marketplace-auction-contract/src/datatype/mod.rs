@@ -1,6 +1,8 @@
 // Export all datatypes
 mod enums;
+mod errors;
 mod models;
 
 pub use enums::*;
+pub use errors::*;
 pub use models::*;
@@ -1,5 +1,8 @@
-use super::enums::{AuctionStatus, DisputeStatus, ProductCondition, ShippingStatus};
-use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+use super::enums::{
+    AuctionStatus, AuctionType, DisputeResolution, DisputeStatus, ProductCondition, SettlementKind,
+    ShippingStatus,
+};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
 /// Product information
 #[contracttype]
@@ -12,7 +15,37 @@ pub struct Product {
     pub images: Vec<String>,
     pub seller: Address,
     pub inventory_count: u32,
-    pub is_authenticated: bool,
+    pub is_authenticated: bool, // Quorum of verifiers currently attest it is authentic; recomputed on read
+    pub attestations: Vec<Attestation>, // Every verdict, oldest first
+}
+
+/// A verifier's verdict on a product; later verdicts by the same verifier supersede earlier ones
+#[contracttype]
+#[derive(Clone)]
+pub struct Attestation {
+    pub verifier: Address,
+    pub is_authentic: bool,
+    pub timestamp: u64,
+    pub notes_hash: BytesN<32>, // Hash of the verifier's off-chain inspection notes
+}
+
+/// One product of a lot as described by the seller
+#[contracttype]
+#[derive(Clone)]
+pub struct LotItemInput {
+    pub name: String,
+    pub description: String,
+    pub condition: ProductCondition,
+    pub images: Vec<String>,
+}
+
+/// One product of a lot, with its own verification and shipping progress
+#[contracttype]
+#[derive(Clone)]
+pub struct LotItem {
+    pub product: Product,
+    pub shipping_status: ShippingStatus,
+    pub delivered_at: u64,
 }
 
 /// Bid information
@@ -25,6 +58,26 @@ pub struct Bid {
     pub quantity: u32,
 }
 
+/// A bidder's latest bid on one auction
+#[contracttype]
+#[derive(Clone)]
+pub struct UserBid {
+    pub auction_id: BytesN<32>,
+    pub amount: i128, // 0 for a sealed bid until it is revealed
+    pub timestamp: u64,
+    pub is_winning: bool, // Derived from the auction when queried
+}
+
+/// Hidden maximum registered for automatic bidding
+#[contracttype]
+#[derive(Clone)]
+pub struct ProxyBid {
+    pub bidder: Address,
+    pub max_amount: i128,
+    pub quantity: u32,
+    pub timestamp: u64,
+}
+
 /// Shipping information
 #[contracttype]
 #[derive(Clone)]
@@ -37,6 +90,218 @@ pub struct ShippingInfo {
     pub recipient_address: String,
 }
 
+/// Orders of at least `min_quantity` units pay `unit_cost` per unit on top of the zone base cost
+#[contracttype]
+#[derive(Clone)]
+pub struct QuantityTier {
+    pub min_quantity: u32,
+    pub unit_cost: i128,
+}
+
+/// Orders of at least `min_quantity` units get `discount_bps` off their shipping cost
+#[contracttype]
+#[derive(Clone)]
+pub struct BulkDiscount {
+    pub min_quantity: u32,
+    pub discount_bps: u32,
+}
+
+/// Admin-managed shipping rate table; zone base costs are stored per zone
+#[contracttype]
+#[derive(Clone)]
+pub struct ShippingRates {
+    pub speed_multipliers: Map<u32, u32>, // Shipping speed -> multiplier in basis points (10000 = 1x)
+    pub quantity_tiers: Vec<QuantityTier>, // Ascending by min_quantity; the last tier reached applies
+    pub bulk_discounts: Vec<BulkDiscount>, // Ascending by min_quantity; the last discount reached applies
+}
+
+/// Shipping cost quoted to the buyer and held in escrow with the winning bid
+#[contracttype]
+#[derive(Clone)]
+pub struct ShippingQuote {
+    pub zone: String,
+    pub speed: u32,
+    pub carrier: Address, // The only carrier that can report progress and delivery
+    pub cost: i128,
+    pub quoted_at: u64,
+}
+
+/// Price schedule for a Dutch auction
+#[contracttype]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DutchSchedule {
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub price_decrement: i128,
+    pub decrement_interval: u64,
+}
+
+impl DutchSchedule {
+    // Unit price at `now` for an auction that started at `start_time`
+    pub fn price_at(&self, start_time: u64, now: u64) -> i128 {
+        let elapsed = now.saturating_sub(start_time);
+        let steps = (elapsed / self.decrement_interval) as i128;
+        let discount = steps.saturating_mul(self.price_decrement);
+        (self.start_price - discount).max(self.floor_price)
+    }
+}
+
+/// Settings for a sealed-bid (commit-reveal) auction
+#[contracttype]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SealedBidConfig {
+    pub reveal_duration: u64, // Seconds after end_time during which bids can be revealed
+    pub deposit: i128,        // Escrowed with each commitment, forfeited if never revealed
+}
+
+/// Sealed bid commitment and, once revealed, its amount. Stored per bidder
+#[contracttype]
+#[derive(Clone)]
+pub struct SealedBid {
+    pub bidder: Address,
+    pub commitment: BytesN<32>, // sha256(amount as 16 big-endian bytes || salt)
+    pub timestamp: u64,
+    pub index: u32, // Order of commitment; the earlier of equal bids wins
+    pub revealed: bool,
+    pub amount: i128,
+}
+
+/// Running count of a sealed-bid auction. The leading revealed bid is held as
+/// the auction's highest bid; every other revealed bid is refunded at once
+#[contracttype]
+#[derive(Clone)]
+pub struct SealedTally {
+    pub commitments: u32,
+    pub reveals: u32,
+    pub second_price: i128, // Highest revealed amount that does not lead
+}
+
+/// Units won by a bidder in a multi-unit auction. Each winner's payment is
+/// delivered, disputed and paid out separately
+#[contracttype]
+#[derive(Clone)]
+pub struct Allocation {
+    pub bidder: Address,
+    pub quantity: u32,
+    pub unit_price: i128,
+    pub delivered_at: u64,     // 0 until the winner confirms receipt
+    pub dispute_deadline: u64, // Last moment to dispute after receipt
+    pub dispute_status: DisputeStatus,
+    pub settled: bool, // Payment has been paid out of escrow
+}
+
+impl Allocation {
+    // Payment held in escrow for these units
+    pub fn amount(&self) -> i128 {
+        self.unit_price * self.quantity as i128
+    }
+}
+
+/// One page of auction IDs from a paginated discovery query
+#[contracttype]
+#[derive(Clone)]
+pub struct AuctionPage {
+    pub auction_ids: Vec<BytesN<32>>,
+    pub next_cursor: u32, // Pass back as `cursor` to continue
+    pub has_more: bool,
+}
+
+/// Optional per-auction settings chosen by the seller at creation
+#[contracttype]
+#[derive(Clone)]
+pub struct AuctionOptions {
+    pub auction_type: AuctionType,
+
+    // Soft close: a bid within `extension_window` seconds of the end pushes
+    // the end out by `extension_duration`, up to `max_extension` in total.
+    // A window of 0 disables it.
+    pub extension_window: u64,
+    pub extension_duration: u64,
+    pub max_extension: u64,
+
+    // Minimum raise over the current highest bid: the larger of an absolute
+    // amount and a share of that bid in basis points. Proxy bids step by it.
+    pub bid_increment: i128,
+    pub bid_increment_bps: u32,
+
+    // Lowest opening bid of an English auction, at most the reserve price so
+    // the reserve can stay hidden. 0 opens at the reserve price.
+    pub starting_price: i128,
+
+    // Buy it now: fixed price that ends the auction immediately, available
+    // until a bid exceeds `buy_now_threshold`. A price of 0 disables it.
+    pub buy_now_price: i128,
+    pub buy_now_threshold: i128,
+
+    // Only accept bids once the product is authenticated
+    pub require_authentication: bool,
+}
+
+/// Platform commission charged when a sale settles to the seller
+#[contracttype]
+#[derive(Clone)]
+pub struct FeeConfig {
+    pub fee_bps: u32,   // Share of the sale in basis points
+    pub flat_fee: i128, // Fixed amount added on top of the percentage
+    pub recipient: Address,
+}
+
+impl FeeConfig {
+    // Fee owed on a sale amount, never more than the sale itself
+    pub fn fee_on(&self, amount: i128) -> i128 {
+        (amount * self.fee_bps as i128 / 10_000 + self.flat_fee).min(amount)
+    }
+}
+
+/// Record of escrowed funds paid out of an auction
+#[contracttype]
+#[derive(Clone)]
+pub struct Settlement {
+    pub recipient: Address,
+    pub amount: i128,
+    pub kind: SettlementKind,
+    pub timestamp: u64,
+}
+
+/// Evidence submitted by the buyer or seller in a dispute
+#[contracttype]
+#[derive(Clone)]
+pub struct Evidence {
+    pub submitter: Address,
+    pub text: String,
+    pub content_hashes: Vec<BytesN<32>>, // Hashes of off-chain files (photos, receipts, ...)
+    pub timestamp: u64,
+}
+
+/// A resolver's decision on a dispute
+#[contracttype]
+#[derive(Clone)]
+pub struct Ruling {
+    pub resolver: Address,
+    pub resolution: DisputeResolution,
+    pub timestamp: u64,
+}
+
+/// Case file for a disputed auction
+#[contracttype]
+#[derive(Clone)]
+pub struct DisputeCase {
+    pub buyer: Address,
+    pub seller: Address,
+    pub reason: String,
+    pub opened_at: u64,
+
+    // The seller must submit evidence by this time or the buyer wins by default
+    pub response_deadline: u64,
+    pub seller_responded: bool,
+
+    pub evidence: Vec<Evidence>,
+
+    // The first ruling and, if appealed, the final one
+    pub rulings: Vec<Ruling>,
+    pub appeal_deadline: u64,
+}
+
 /// Standard auction data (flattened)
 #[contracttype]
 #[derive(Clone)]
@@ -55,7 +320,8 @@ pub struct Auction {
     pub highest_bid_timestamp: u64,
     pub highest_bid_quantity: u32,
 
-    pub all_bids: Vec<Bid>,
+    // Bids are stored in separate pages; this is the total number placed
+    pub bid_count: u32,
 
     // For shipping
     pub has_shipping: bool,
@@ -65,12 +331,46 @@ pub struct Auction {
     pub shipping_delivery_estimate: u64,
     pub shipping_cost: i128,
     pub shipping_recipient: String,
+    pub delivered_at: u64,
+    pub dispute_deadline: u64, // Buyer may dispute until this time once delivered
+
+    // Shipping quote the buyer paid for; required before the item ships
+    pub has_shipping_quote: bool,
+    pub shipping_quote: ShippingQuote,
 
     pub dispute_status: DisputeStatus,
 
     // For dispute_reason
     pub has_dispute_reason: bool,
     pub dispute_reason: String,
+
+    // Escrow: token used for payment and the amount currently held by the contract
+    pub payment_token: Address,
+    pub escrowed_amount: i128,
+
+    // Platform fee in force when the auction was created
+    pub fee: FeeConfig,
+
+    // Audit trail of every payout made from escrow
+    pub settlements: Vec<Settlement>,
+
+    // Commitments for sealed-bid auctions are stored per bidder
+    pub sealed: SealedTally,
+
+    // Multi-unit clearing outcome
+    pub clearing_price: i128,
+    pub allocations: Vec<Allocation>,
+
+    // Seller-chosen settings and the latest end time soft close may reach
+    pub options: AuctionOptions,
+    pub max_end_time: u64,
+
+    // For relisted_from: the unsold auction this one relists
+    pub has_relisted_from: bool,
+    pub relisted_from: BytesN<32>,
+
+    // Number of products in a lot auction (stored separately), 0 for a single product
+    pub lot_size: u32,
 }
 
 impl Auction {
@@ -103,6 +403,23 @@ impl Auction {
         }
     }
 
+    // Quantity that ships: every product of a lot, otherwise the winning
+    // quantity, or a single unit before there is a winner
+    pub fn shipping_quantity(&self) -> u32 {
+        if self.lot_size > 0 {
+            self.lot_size
+        } else if self.has_highest_bid {
+            self.highest_bid_quantity
+        } else {
+            1
+        }
+    }
+
+    // Whether the auction has a winning bid at or above the reserve price
+    pub fn reserve_met(&self) -> bool {
+        self.has_highest_bid && self.highest_bid_amount >= self.reserve_price
+    }
+
     // Get shipping info if it exists
     pub fn shipping_info(&self) -> Option<ShippingInfo> {
         if self.has_shipping {
@@ -168,8 +485,12 @@ impl Auction {
         start_time: u64,
         end_time: u64,
         reserve_price: i128,
+        payment_token: Address,
+        fee: FeeConfig,
+        options: AuctionOptions,
     ) -> Self {
         Auction {
+            relisted_from: id.clone(),
             id,
             product,
             status: AuctionStatus::Pending,
@@ -183,7 +504,7 @@ impl Auction {
             highest_bid_timestamp: 0,
             highest_bid_quantity: 0,
 
-            all_bids: Vec::new(env),
+            bid_count: 0,
 
             has_shipping: false,
             shipping_status: ShippingStatus::NotShipped,
@@ -192,11 +513,46 @@ impl Auction {
             shipping_delivery_estimate: 0,
             shipping_cost: 0,
             shipping_recipient: String::from_str(env, ""),
+            delivered_at: 0,
+            dispute_deadline: 0,
+
+            has_shipping_quote: false,
+            shipping_quote: ShippingQuote {
+                zone: String::from_str(env, ""),
+                speed: 0,
+                carrier: env.current_contract_address(),
+                cost: 0,
+                quoted_at: 0,
+            },
 
             dispute_status: DisputeStatus::None,
 
             has_dispute_reason: false,
             dispute_reason: String::from_str(env, ""),
+
+            payment_token,
+            escrowed_amount: 0,
+
+            fee,
+
+            settlements: Vec::new(env),
+
+            sealed: SealedTally {
+                commitments: 0,
+                reveals: 0,
+                second_price: 0,
+            },
+
+            clearing_price: 0,
+            allocations: Vec::new(env),
+
+            // Overflow is rejected by validate_listing
+            max_end_time: end_time.saturating_add(options.max_extension),
+            options,
+
+            has_relisted_from: false,
+
+            lot_size: 0,
         }
     }
 }
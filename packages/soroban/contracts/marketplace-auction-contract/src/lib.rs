@@ -1,11 +1,17 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+// Contract entry points mirror the full set of auction parameters
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
 
 mod datatype;
 mod operations;
 
 pub use datatype::{
-    Auction, AuctionStatus, Bid, DisputeStatus, ProductCondition, ShippingInfo, ShippingStatus,
+    Allocation, Attestation, Auction, AuctionError, AuctionOptions, AuctionPage, AuctionStatus,
+    AuctionType, Bid, BulkDiscount, DisputeCase, DisputeResolution, DisputeStatus, DutchSchedule,
+    Evidence, FeeConfig, LotItem, LotItemInput, ProductCondition, QuantityTier, Ruling, SealedBid,
+    SealedBidConfig, SealedTally, Settlement, SettlementKind, ShippingInfo, ShippingQuote,
+    ShippingRates, ShippingStatus, UserBid,
 };
 
 #[contract]
@@ -13,9 +19,13 @@ pub struct AuctionContract;
 
 #[contractimpl]
 impl AuctionContract {
-    // Initialize the contract with an admin
-    pub fn initialize(env: Env, admin: Address) {
-        operations::initialize(&env, &admin);
+    // Initialize the contract with an admin and the SEP-41 token bids are paid in
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        payment_token: Address,
+    ) -> Result<(), AuctionError> {
+        operations::initialize(&env, &admin, &payment_token)
     }
 
     // Create a new auction for a product
@@ -30,7 +40,8 @@ impl AuctionContract {
         reserve_price: i128,
         start_time: u64,
         end_time: u64,
-    ) -> BytesN<32> {
+        options: AuctionOptions,
+    ) -> Result<BytesN<32>, AuctionError> {
         operations::create_auction(
             &env,
             &seller,
@@ -42,12 +53,13 @@ impl AuctionContract {
             &reserve_price,
             &start_time,
             &end_time,
+            &options,
         )
     }
 
     // Start an auction (transition from Pending to Active)
-    pub fn start_auction(env: Env, auction_id: BytesN<32>) {
-        operations::start_auction(&env, &auction_id);
+    pub fn start_auction(env: Env, auction_id: BytesN<32>) -> Result<(), AuctionError> {
+        operations::start_auction(&env, &auction_id)
     }
 
     // Place a bid on an auction
@@ -57,74 +69,496 @@ impl AuctionContract {
         bidder: Address,
         amount: i128,
         quantity: u32,
-    ) {
-        operations::place_bid(&env, &auction_id, &bidder, &amount, &quantity);
+    ) -> Result<(), AuctionError> {
+        operations::place_bid(&env, &auction_id, &bidder, &amount, &quantity)
+    }
+
+    // Register a hidden maximum bid; the contract outbids competitors by the
+    // auction's bid increment up to that maximum
+    pub fn place_proxy_bid(
+        env: Env,
+        auction_id: BytesN<32>,
+        bidder: Address,
+        max_amount: i128,
+        quantity: u32,
+    ) -> Result<(), AuctionError> {
+        operations::place_proxy_bid(&env, &auction_id, &bidder, &max_amount, &quantity)
+    }
+
+    // Buy at the current price of a Dutch auction (first buyer wins)
+    pub fn accept_price(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        quantity: u32,
+    ) -> Result<(), AuctionError> {
+        operations::accept_price(&env, &auction_id, &buyer, &quantity)
+    }
+
+    // Commit a sealed bid: sha256(amount as 16 big-endian bytes || salt)
+    pub fn commit_bid(
+        env: Env,
+        auction_id: BytesN<32>,
+        bidder: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), AuctionError> {
+        operations::commit_bid(&env, &auction_id, &bidder, &commitment)
+    }
+
+    // Reveal a sealed bid during the reveal phase
+    pub fn reveal_bid(
+        env: Env,
+        auction_id: BytesN<32>,
+        bidder: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<(), AuctionError> {
+        operations::reveal_bid(&env, &auction_id, &bidder, &amount, &salt)
+    }
+
+    // Pay the buy-it-now price, ending the auction immediately
+    pub fn buy_now(env: Env, auction_id: BytesN<32>, buyer: Address) -> Result<(), AuctionError> {
+        operations::buy_now(&env, &auction_id, &buyer)
     }
 
     // End an auction (can be called by anyone after end_time)
-    pub fn end_auction(env: Env, auction_id: BytesN<32>) {
-        operations::end_auction(&env, &auction_id);
+    pub fn end_auction(env: Env, auction_id: BytesN<32>) -> Result<(), AuctionError> {
+        operations::end_auction(&env, &auction_id)
+    }
+
+    // Create an auction selling several products together as one lot
+    pub fn create_lot_auction(
+        env: Env,
+        seller: Address,
+        name: String,
+        description: String,
+        items: Vec<LotItemInput>,
+        reserve_price: i128,
+        start_time: u64,
+        end_time: u64,
+        options: AuctionOptions,
+    ) -> Result<BytesN<32>, AuctionError> {
+        operations::create_lot_auction(
+            &env,
+            &seller,
+            &name,
+            &description,
+            &items,
+            &reserve_price,
+            &start_time,
+            &end_time,
+            &options,
+        )
+    }
+
+    // Post a service request (reverse auction) open for offers until the deadline
+    pub fn create_service_request(
+        env: Env,
+        requester: Address,
+        subject: String,
+        description: String,
+        max_budget: i128,
+        deadline: u64,
+    ) -> Result<BytesN<32>, AuctionError> {
+        operations::create_service_request(
+            &env,
+            &requester,
+            &subject,
+            &description,
+            &max_budget,
+            &deadline,
+        )
+    }
+
+    // Offer to fulfil a service request within its budget
+    pub fn place_offer(
+        env: Env,
+        auction_id: BytesN<32>,
+        provider: Address,
+        amount: i128,
+    ) -> Result<(), AuctionError> {
+        operations::place_offer(&env, &auction_id, &provider, &amount)
+    }
+
+    // Accept any offer on a service request (requester only)
+    pub fn accept_offer(
+        env: Env,
+        auction_id: BytesN<32>,
+        provider: Address,
+    ) -> Result<(), AuctionError> {
+        operations::accept_offer(&env, &auction_id, &provider)
+    }
+
+    // Relist an unsold, expired or cancelled auction; reserve_price 0 keeps the old reserve
+    pub fn relist_auction(
+        env: Env,
+        auction_id: BytesN<32>,
+        start_time: u64,
+        end_time: u64,
+        reserve_price: i128,
+    ) -> Result<BytesN<32>, AuctionError> {
+        operations::relist_auction(&env, &auction_id, &start_time, &end_time, &reserve_price)
     }
 
     // Cancel an auction (only possible in Pending status)
-    pub fn cancel_auction(env: Env, auction_id: BytesN<32>) {
-        operations::cancel_auction(&env, &auction_id);
+    pub fn cancel_auction(env: Env, auction_id: BytesN<32>) -> Result<(), AuctionError> {
+        operations::cancel_auction(&env, &auction_id)
+    }
+
+    // Attest to product authenticity with a hash of the inspection notes (verifiers only)
+    pub fn verify_product(
+        env: Env,
+        verifier: Address,
+        auction_id: BytesN<32>,
+        is_authentic: bool,
+        notes_hash: BytesN<32>,
+    ) -> Result<(), AuctionError> {
+        operations::verify_product(&env, &verifier, &auction_id, &is_authentic, &notes_hash)
+    }
+
+    // Attest to one item of a lot (verifiers only)
+    pub fn verify_lot_item(
+        env: Env,
+        verifier: Address,
+        auction_id: BytesN<32>,
+        index: u32,
+        is_authentic: bool,
+        notes_hash: BytesN<32>,
+    ) -> Result<(), AuctionError> {
+        operations::verify_lot_item(
+            &env,
+            &verifier,
+            &auction_id,
+            &index,
+            &is_authentic,
+            &notes_hash,
+        )
     }
 
-    // Verify product authenticity (only verifiers can do this)
-    pub fn verify_product(env: Env, verifier: Address, auction_id: BytesN<32>, is_authentic: bool) {
-        operations::verify_product(&env, &verifier, &auction_id, &is_authentic);
+    // Quote shipping and escrow its cost (winning bidder only)
+    pub fn quote_shipping(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        zone: String,
+        speed: u32,
+        carrier: Address,
+    ) -> Result<i128, AuctionError> {
+        operations::quote_shipping(&env, &auction_id, &buyer, &zone, &speed, &carrier)
     }
 
-    // Add shipping information (seller only)
+    // Add shipping information for the quoted shipment (seller only)
     pub fn add_shipping_info(
         env: Env,
         auction_id: BytesN<32>,
         tracking_number: String,
         carrier: String,
         estimated_delivery: u64,
-        shipping_cost: i128,
         recipient_address: String,
-    ) {
+    ) -> Result<(), AuctionError> {
         operations::add_shipping_info(
             &env,
             &auction_id,
             &tracking_number,
             &carrier,
             &estimated_delivery,
-            &shipping_cost,
             &recipient_address,
-        );
+        )
+    }
+
+    // Choose the carriers a seller ships with (seller only; empty accepts any carrier)
+    pub fn set_seller_carriers(
+        env: Env,
+        seller: Address,
+        carriers: Vec<Address>,
+    ) -> Result<(), AuctionError> {
+        operations::set_carriers_for_seller(&env, &seller, &carriers)
+    }
+
+    // Update shipping status (seller or the quoted carrier; only the carrier can report delivery)
+    pub fn update_shipping_status(
+        env: Env,
+        auction_id: BytesN<32>,
+        caller: Address,
+        new_status: ShippingStatus,
+    ) -> Result<(), AuctionError> {
+        operations::update_shipping_status(&env, &auction_id, &caller, &new_status)
+    }
+
+    // Confirm delivery (winning bidder only)
+    pub fn confirm_delivery(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+    ) -> Result<(), AuctionError> {
+        operations::confirm_delivery(&env, &auction_id, &buyer)
     }
 
-    // Update shipping status
-    pub fn update_shipping_status(env: Env, auction_id: BytesN<32>, new_status: ShippingStatus) {
-        operations::update_shipping_status(&env, &auction_id, &new_status);
+    // Update the shipping status of one item of a lot (seller or the quoted carrier)
+    pub fn update_lot_item_status(
+        env: Env,
+        auction_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        new_status: ShippingStatus,
+    ) -> Result<(), AuctionError> {
+        operations::update_lot_item_status(&env, &auction_id, &caller, &index, &new_status)
+    }
+
+    // Confirm delivery of one item of a lot (winning bidder only)
+    pub fn confirm_lot_item_delivery(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        index: u32,
+    ) -> Result<(), AuctionError> {
+        operations::confirm_lot_item_delivery(&env, &auction_id, &buyer, &index)
+    }
+
+    // Complete a delivered auction once the dispute window has passed
+    pub fn finalize_delivery(env: Env, auction_id: BytesN<32>) -> Result<(), AuctionError> {
+        operations::finalize_delivery(&env, &auction_id)
     }
 
     // Open a dispute (buyer only)
-    pub fn open_dispute(env: Env, auction_id: BytesN<32>, buyer: Address, reason: String) {
-        operations::open_dispute(&env, &auction_id, &buyer, &reason);
+    pub fn open_dispute(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        reason: String,
+    ) -> Result<(), AuctionError> {
+        operations::open_dispute(&env, &auction_id, &buyer, &reason)
     }
 
-    // Resolve a dispute (admin or dispute resolver only)
+    // Add evidence to a dispute case file (buyer or seller)
+    pub fn submit_evidence(
+        env: Env,
+        auction_id: BytesN<32>,
+        party: Address,
+        text: String,
+        content_hashes: Vec<BytesN<32>>,
+    ) -> Result<(), AuctionError> {
+        operations::submit_evidence(&env, &auction_id, &party, &text, &content_hashes)
+    }
+
+    // Rule on a dispute (admin or dispute resolver only); rulings on appeal settle the escrow
     pub fn resolve_dispute(
         env: Env,
         resolver: Address,
         auction_id: BytesN<32>,
-        resolution: DisputeStatus,
-    ) {
-        operations::resolve_dispute(&env, &resolver, &auction_id, &resolution);
+        resolution: DisputeResolution,
+    ) -> Result<(), AuctionError> {
+        operations::resolve_dispute(&env, &resolver, &auction_id, &resolution)
+    }
+
+    // Appeal a ruling to a different resolver (buyer or seller)
+    pub fn appeal_dispute(
+        env: Env,
+        auction_id: BytesN<32>,
+        party: Address,
+    ) -> Result<(), AuctionError> {
+        operations::appeal_dispute(&env, &auction_id, &party)
+    }
+
+    // Settle an unappealed ruling once the appeal window has passed
+    pub fn finalize_dispute(env: Env, auction_id: BytesN<32>) -> Result<(), AuctionError> {
+        operations::finalize_dispute(&env, &auction_id)
+    }
+
+    // Refund the buyer when the seller missed the response deadline
+    pub fn apply_default_judgment(env: Env, auction_id: BytesN<32>) -> Result<(), AuctionError> {
+        operations::apply_default_judgment(&env, &auction_id)
+    }
+
+    // Confirm receipt of the units won in a multi-unit auction (that winner only)
+    pub fn confirm_allocation_delivery(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+    ) -> Result<(), AuctionError> {
+        operations::confirm_allocation_delivery(&env, &auction_id, &buyer)
+    }
+
+    // Pay the seller for a winner's units once the dispute window has passed
+    pub fn finalize_allocation(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+    ) -> Result<(), AuctionError> {
+        operations::finalize_allocation(&env, &auction_id, &buyer)
+    }
+
+    // Dispute a multi-unit winner's units (that winner only)
+    pub fn open_allocation_dispute(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        reason: String,
+    ) -> Result<(), AuctionError> {
+        operations::open_allocation_dispute(&env, &auction_id, &buyer, &reason)
+    }
+
+    // Add evidence to a winner's allocation dispute (that winner or the seller)
+    pub fn submit_allocation_evidence(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        party: Address,
+        text: String,
+        content_hashes: Vec<BytesN<32>>,
+    ) -> Result<(), AuctionError> {
+        operations::submit_allocation_evidence(
+            &env,
+            &auction_id,
+            &buyer,
+            &party,
+            &text,
+            &content_hashes,
+        )
+    }
+
+    // Rule on a winner's allocation dispute (admin or dispute resolver only)
+    pub fn resolve_allocation_dispute(
+        env: Env,
+        resolver: Address,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        resolution: DisputeResolution,
+    ) -> Result<(), AuctionError> {
+        operations::resolve_allocation_dispute(&env, &resolver, &auction_id, &buyer, &resolution)
+    }
+
+    // Appeal the ruling on a winner's allocation dispute (that winner or the seller)
+    pub fn appeal_allocation_dispute(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+        party: Address,
+    ) -> Result<(), AuctionError> {
+        operations::appeal_allocation_dispute(&env, &auction_id, &buyer, &party)
+    }
+
+    // Settle an unappealed ruling on a winner's allocation after the appeal window
+    pub fn finalize_allocation_dispute(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+    ) -> Result<(), AuctionError> {
+        operations::finalize_allocation_dispute(&env, &auction_id, &buyer)
+    }
+
+    // Refund a winner when the seller missed the allocation dispute's response deadline
+    pub fn allocation_default_judgment(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+    ) -> Result<(), AuctionError> {
+        operations::allocation_default_judgment(&env, &auction_id, &buyer)
     }
 
     // Add a product verifier (admin only)
-    pub fn add_verifier(env: Env, admin: Address, verifier: Address) {
-        operations::add_verifier(&env, &admin, &verifier);
+    pub fn add_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), AuctionError> {
+        operations::add_verifier(&env, &admin, &verifier)
     }
 
     // Add a dispute resolver (admin only)
-    pub fn add_resolver(env: Env, admin: Address, resolver: Address) {
-        operations::add_resolver(&env, &admin, &resolver);
+    pub fn add_resolver(env: Env, admin: Address, resolver: Address) -> Result<(), AuctionError> {
+        operations::add_resolver(&env, &admin, &resolver)
+    }
+
+    // Add a carrier that can report delivery (admin only)
+    pub fn add_carrier(env: Env, admin: Address, carrier: Address) -> Result<(), AuctionError> {
+        operations::add_carrier(&env, &admin, &carrier)
+    }
+
+    // Remove a product verifier (admin only)
+    pub fn remove_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+    ) -> Result<(), AuctionError> {
+        operations::remove_verifier(&env, &admin, &verifier)
+    }
+
+    // Remove a dispute resolver (admin only)
+    pub fn remove_resolver(
+        env: Env,
+        admin: Address,
+        resolver: Address,
+    ) -> Result<(), AuctionError> {
+        operations::remove_resolver(&env, &admin, &resolver)
+    }
+
+    // Remove a carrier (admin only)
+    pub fn remove_carrier(env: Env, admin: Address, carrier: Address) -> Result<(), AuctionError> {
+        operations::remove_carrier(&env, &admin, &carrier)
+    }
+
+    // Propose a new admin (admin only); takes effect once accepted
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), AuctionError> {
+        operations::propose_admin(&env, &admin, &new_admin)
+    }
+
+    // Accept a pending admin proposal (proposed admin only)
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), AuctionError> {
+        operations::accept_admin(&env, &new_admin)
+    }
+
+    // Set the dispute window after delivery, in seconds (admin only)
+    pub fn set_dispute_window(env: Env, admin: Address, seconds: u64) -> Result<(), AuctionError> {
+        operations::set_dispute_window(&env, &admin, &seconds)
+    }
+
+    // Set the seller response and appeal windows for disputes, in seconds (admin only)
+    pub fn set_dispute_deadlines(
+        env: Env,
+        admin: Address,
+        response_window: u64,
+        appeal_window: u64,
+    ) -> Result<(), AuctionError> {
+        operations::set_dispute_deadlines(&env, &admin, &response_window, &appeal_window)
+    }
+
+    // Set the base shipping cost of a zone code (admin only)
+    pub fn set_shipping_zone(
+        env: Env,
+        admin: Address,
+        zone: String,
+        base_cost: i128,
+    ) -> Result<(), AuctionError> {
+        operations::set_zone_rate(&env, &admin, &zone, &base_cost)
+    }
+
+    // Remove a shipping zone (admin only)
+    pub fn remove_shipping_zone(
+        env: Env,
+        admin: Address,
+        zone: String,
+    ) -> Result<(), AuctionError> {
+        operations::remove_zone_rate(&env, &admin, &zone)
+    }
+
+    // Set shipping speed multipliers, quantity tiers and bulk discounts (admin only)
+    pub fn set_shipping_rates(
+        env: Env,
+        admin: Address,
+        rates: ShippingRates,
+    ) -> Result<(), AuctionError> {
+        operations::set_shipping_rates(&env, &admin, &rates)
+    }
+
+    // Set how many positive attestations authenticate a product (admin only)
+    pub fn set_attestation_quorum(
+        env: Env,
+        admin: Address,
+        quorum: u32,
+    ) -> Result<(), AuctionError> {
+        operations::set_attestation_quorum(&env, &admin, &quorum)
+    }
+
+    // Set the platform fee for auctions created from now on (admin only)
+    pub fn set_fee_config(env: Env, admin: Address, config: FeeConfig) -> Result<(), AuctionError> {
+        operations::set_fee_config(&env, &admin, &config)
     }
 
     // Get auction details
@@ -137,24 +571,176 @@ impl AuctionContract {
         operations::query_user_selling_auctions(&env, &user)
     }
 
+    // Get the products of a lot with their verification and shipping progress
+    pub fn get_lot(env: Env, auction_id: BytesN<32>) -> Result<Vec<LotItem>, AuctionError> {
+        operations::query_lot(&env, &auction_id)
+    }
+
+    // Get every auction that listed a product, oldest first (relist lineage)
+    pub fn get_product_lineage(env: Env, product_id: BytesN<32>) -> Vec<BytesN<32>> {
+        operations::query_product_lineage(&env, &product_id)
+    }
+
     // Get auctions where user has bid
     pub fn get_user_bidding_auctions(env: Env, user: Address) -> Vec<BytesN<32>> {
         operations::query_user_bidding_auctions(&env, &user)
     }
 
+    // Get a bidder's latest bid on an auction and whether it is winning
+    pub fn get_user_bid(env: Env, auction_id: BytesN<32>, bidder: Address) -> Option<UserBid> {
+        operations::query_user_bid(&env, &auction_id, &bidder)
+    }
+
+    // Paginated: running auctions where the user holds a winning bid
+    pub fn get_user_winning_auctions(
+        env: Env,
+        user: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> AuctionPage {
+        operations::query_user_winning_auctions(&env, &user, &cursor, &limit)
+    }
+
+    // Paginated: auctions where the user has been outbid
+    pub fn get_user_outbid_auctions(
+        env: Env,
+        user: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> AuctionPage {
+        operations::query_user_outbid_auctions(&env, &user, &cursor, &limit)
+    }
+
+    // Paginated: auctions the user has won
+    pub fn get_user_won_auctions(env: Env, user: Address, cursor: u32, limit: u32) -> AuctionPage {
+        operations::query_user_won_auctions(&env, &user, &cursor, &limit)
+    }
+
     // Bulk operations: get multiple auctions at once
     pub fn get_auctions(env: Env, auction_ids: Vec<BytesN<32>>) -> Vec<Auction> {
         operations::query_auctions(&env, &auction_ids)
     }
 
-    // Calculate shipping based on location
+    // Get one page of an auction's bid history
+    pub fn get_bids(env: Env, auction_id: BytesN<32>, page: u32) -> Vec<Bid> {
+        operations::query_bids(&env, &auction_id, &page)
+    }
+
+    // Paginated discovery: active auctions
+    pub fn get_active_auctions(env: Env, cursor: u32, limit: u32) -> AuctionPage {
+        operations::query_active_auctions(&env, &cursor, &limit)
+    }
+
+    // Paginated discovery: pending or active auctions ending before a timestamp
+    pub fn get_auctions_ending_before(
+        env: Env,
+        timestamp: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> AuctionPage {
+        operations::query_auctions_ending_before(&env, &timestamp, &cursor, &limit)
+    }
+
+    // Paginated discovery: auctions in a given status
+    pub fn get_auctions_by_status(
+        env: Env,
+        status: AuctionStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> AuctionPage {
+        operations::query_auctions_by_status(&env, &status, &cursor, &limit)
+    }
+
+    // Get the allocation table of a cleared multi-unit auction
+    pub fn get_allocations(
+        env: Env,
+        auction_id: BytesN<32>,
+    ) -> Result<Vec<Allocation>, AuctionError> {
+        operations::query_allocations(&env, &auction_id)
+    }
+
+    // Get the current admin
+    pub fn get_admin(env: Env) -> Result<Address, AuctionError> {
+        operations::query_admin(&env)
+    }
+
+    // List product verifiers
+    pub fn get_verifiers(env: Env) -> Vec<Address> {
+        operations::query_verifiers(&env)
+    }
+
+    // List dispute resolvers
+    pub fn get_resolvers(env: Env) -> Vec<Address> {
+        operations::query_resolvers(&env)
+    }
+
+    // List carriers
+    pub fn get_carriers(env: Env) -> Vec<Address> {
+        operations::query_carriers(&env)
+    }
+
+    // Get how many positive attestations authenticate a product
+    pub fn get_attestation_quorum(env: Env) -> u32 {
+        operations::query_attestation_quorum(&env)
+    }
+
+    // Get the current platform fee configuration
+    pub fn get_fee_config(env: Env) -> FeeConfig {
+        operations::query_fee_config(&env)
+    }
+
+    // Get the total platform fees collected in a payment token
+    pub fn get_total_fees(env: Env, token: Address) -> i128 {
+        operations::query_total_fees(&env, &token)
+    }
+
+    // Get the dispute case file of an auction
+    pub fn get_dispute(env: Env, auction_id: BytesN<32>) -> Result<DisputeCase, AuctionError> {
+        operations::query_dispute(&env, &auction_id)
+    }
+
+    // Get the dispute case file of one winner's allocation on a multi-unit auction
+    pub fn get_allocation_dispute(
+        env: Env,
+        auction_id: BytesN<32>,
+        buyer: Address,
+    ) -> Result<DisputeCase, AuctionError> {
+        operations::query_allocation_dispute(&env, &auction_id, &buyer)
+    }
+
+    // Get the currently visible price of an auction
+    pub fn get_visible_price(env: Env, auction_id: BytesN<32>) -> Result<i128, AuctionError> {
+        operations::query_visible_price(&env, &auction_id)
+    }
+
+    // Get the current unit price of a Dutch auction
+    pub fn get_current_price(env: Env, auction_id: BytesN<32>) -> Result<i128, AuctionError> {
+        operations::query_current_price(&env, &auction_id)
+    }
+
+    // Calculate shipping for a zone and speed from the rate table
     pub fn calculate_shipping_cost(
         env: Env,
         auction_id: BytesN<32>,
-        destination: String,
+        zone: String,
         shipping_speed: u32,
-    ) -> i128 {
-        operations::calculate_shipping_cost(&env, &auction_id, &destination, &shipping_speed)
+    ) -> Result<i128, AuctionError> {
+        operations::calculate_shipping_cost(&env, &auction_id, &zone, &shipping_speed)
+    }
+
+    // Get the shipping rate table
+    pub fn get_shipping_rates(env: Env) -> ShippingRates {
+        operations::query_shipping_rates(&env)
+    }
+
+    // Get the base shipping cost of a zone
+    pub fn get_shipping_zone(env: Env, zone: String) -> Option<i128> {
+        operations::query_shipping_zone(&env, &zone)
+    }
+
+    // Get the carriers a seller ships with
+    pub fn get_seller_carriers(env: Env, seller: Address) -> Vec<Address> {
+        operations::query_seller_carriers(&env, &seller)
     }
 }
 
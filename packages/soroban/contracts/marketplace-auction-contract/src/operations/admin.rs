@@ -1,20 +1,25 @@
-use super::storage::get_admin;
-use crate::datatype::StorageKey;
-use soroban_sdk::{Address, Env, Vec};
+use super::storage::{
+    get_admin, get_role_members, get_shipping_zone, remove_shipping_zone, set_shipping_zone,
+};
+use crate::datatype::{AuctionError, FeeConfig, ShippingRates, StorageKey};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
-// Initialize the contract with an admin
-pub fn initialize(env: &Env, admin: &Address) {
+// Initialize the contract with an admin and the token used for bid escrow
+pub fn initialize(env: &Env, admin: &Address, payment_token: &Address) -> Result<(), AuctionError> {
     if env.storage().instance().has(&StorageKey::Admin) {
-        panic!("Contract already initialized");
+        return Err(AuctionError::AlreadyInitialized);
     }
 
     admin.require_auth();
     env.storage().instance().set(&StorageKey::Admin, admin);
+    env.storage()
+        .instance()
+        .set(&StorageKey::PaymentToken, payment_token);
     env.storage()
         .instance()
         .set(&StorageKey::AuctionCounter, &0u32);
 
-    // Initialize empty verifiers and resolvers lists
+    // Initialize empty verifiers, resolvers and carriers lists
     env.storage()
         .instance()
         .set(&StorageKey::Verifiers, &Vec::<Address>::new(env));
@@ -22,54 +27,276 @@ pub fn initialize(env: &Env, admin: &Address) {
     env.storage()
         .instance()
         .set(&StorageKey::Resolvers, &Vec::<Address>::new(env));
+
+    env.storage()
+        .instance()
+        .set(&StorageKey::Carriers, &Vec::<Address>::new(env));
+
+    Ok(())
 }
 
-// Add a verifier (admin only)
-pub fn add_verifier(env: &Env, admin: &Address, verifier: &Address) {
+// Check that the caller is the current admin
+fn require_admin(env: &Env, admin: &Address) -> Result<(), AuctionError> {
     admin.require_auth();
 
-    // Check if caller is admin
-    let stored_admin = get_admin(env);
+    let stored_admin = get_admin(env)?;
     if *admin != stored_admin {
-        panic!("Only admin can add verifiers");
+        return Err(AuctionError::Unauthorized);
     }
 
-    // Add to verifiers list if not already present
-    let mut verifiers: Vec<Address> = env
+    Ok(())
+}
+
+// Add an address to a role list if not already present
+fn grant_role(env: &Env, role: StorageKey, member: &Address, event: &str) {
+    let mut members = get_role_members(env, &role);
+
+    if !members.contains(member) {
+        members.push_back(member.clone());
+        env.storage().instance().set(&role, &members);
+
+        // Emit event
+        env.events()
+            .publish((Symbol::new(env, event),), member.clone());
+    }
+}
+
+// Remove an address from a role list
+fn revoke_role(
+    env: &Env,
+    role: StorageKey,
+    member: &Address,
+    event: &str,
+) -> Result<(), AuctionError> {
+    let mut members = get_role_members(env, &role);
+
+    let index = members
+        .first_index_of(member)
+        .ok_or(AuctionError::RoleNotAssigned)?;
+    members.remove(index);
+    env.storage().instance().set(&role, &members);
+
+    // Emit event
+    env.events()
+        .publish((Symbol::new(env, event),), member.clone());
+
+    Ok(())
+}
+
+// Add a verifier (admin only)
+pub fn add_verifier(env: &Env, admin: &Address, verifier: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+    grant_role(env, StorageKey::Verifiers, verifier, "verifier_added");
+    Ok(())
+}
+
+// Remove a verifier (admin only)
+pub fn remove_verifier(env: &Env, admin: &Address, verifier: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+    revoke_role(env, StorageKey::Verifiers, verifier, "verifier_removed")
+}
+
+// Add a resolver (admin only)
+pub fn add_resolver(env: &Env, admin: &Address, resolver: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+    grant_role(env, StorageKey::Resolvers, resolver, "resolver_added");
+    Ok(())
+}
+
+// Remove a resolver (admin only)
+pub fn remove_resolver(env: &Env, admin: &Address, resolver: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+    revoke_role(env, StorageKey::Resolvers, resolver, "resolver_removed")
+}
+
+// Add a carrier allowed to report delivery (admin only)
+pub fn add_carrier(env: &Env, admin: &Address, carrier: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+    grant_role(env, StorageKey::Carriers, carrier, "carrier_added");
+    Ok(())
+}
+
+// Remove a carrier (admin only)
+pub fn remove_carrier(env: &Env, admin: &Address, carrier: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+    revoke_role(env, StorageKey::Carriers, carrier, "carrier_removed")
+}
+
+// Propose a new admin; the handover completes when they accept (admin only).
+// Proposing again replaces the pending proposal
+pub fn propose_admin(env: &Env, admin: &Address, new_admin: &Address) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    env.storage()
+        .instance()
+        .set(&StorageKey::PendingAdmin, new_admin);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "admin_proposed"),),
+        (admin.clone(), new_admin.clone()),
+    );
+
+    Ok(())
+}
+
+// Accept a pending admin proposal (proposed admin only)
+pub fn accept_admin(env: &Env, new_admin: &Address) -> Result<(), AuctionError> {
+    new_admin.require_auth();
+
+    let pending: Address = env
         .storage()
         .instance()
-        .get(&StorageKey::Verifiers)
-        .unwrap_or_else(|| Vec::new(env));
-
-    if !verifiers.contains(verifier) {
-        verifiers.push_back(verifier.clone());
-        env.storage()
-            .instance()
-            .set(&StorageKey::Verifiers, &verifiers);
+        .get(&StorageKey::PendingAdmin)
+        .ok_or(AuctionError::NoPendingAdmin)?;
+    if pending != *new_admin {
+        return Err(AuctionError::Unauthorized);
     }
+
+    let previous_admin = get_admin(env)?;
+    env.storage().instance().set(&StorageKey::Admin, new_admin);
+    env.storage().instance().remove(&StorageKey::PendingAdmin);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "admin_transferred"),),
+        (previous_admin, new_admin.clone()),
+    );
+
+    Ok(())
 }
 
-// Add a resolver (admin only)
-pub fn add_resolver(env: &Env, admin: &Address, resolver: &Address) {
-    admin.require_auth();
+// Set how long buyers have to dispute after delivery (admin only)
+pub fn set_dispute_window(env: &Env, admin: &Address, seconds: &u64) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
 
-    // Check if caller is admin
-    let stored_admin = get_admin(env);
-    if *admin != stored_admin {
-        panic!("Only admin can add resolvers");
+    env.storage()
+        .instance()
+        .set(&StorageKey::DisputeWindow, seconds);
+
+    Ok(())
+}
+
+// Set how long sellers have to answer a dispute and parties have to appeal a ruling (admin only)
+pub fn set_dispute_deadlines(
+    env: &Env,
+    admin: &Address,
+    response_window: &u64,
+    appeal_window: &u64,
+) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    env.storage()
+        .instance()
+        .set(&StorageKey::ResponseWindow, response_window);
+    env.storage()
+        .instance()
+        .set(&StorageKey::AppealWindow, appeal_window);
+
+    Ok(())
+}
+
+// Set how many positive attestations authenticate a product (admin only)
+pub fn set_attestation_quorum(
+    env: &Env,
+    admin: &Address,
+    quorum: &u32,
+) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    if *quorum == 0 {
+        return Err(AuctionError::InvalidConfig);
     }
+    env.storage()
+        .instance()
+        .set(&StorageKey::AttestationQuorum, quorum);
 
-    // Add to resolvers list if not already present
-    let mut resolvers: Vec<Address> = env
-        .storage()
+    Ok(())
+}
+
+// Set the base shipping cost of a zone code (admin only)
+pub fn set_zone_rate(
+    env: &Env,
+    admin: &Address,
+    zone: &String,
+    base_cost: &i128,
+) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    if *base_cost < 0 {
+        return Err(AuctionError::InvalidConfig);
+    }
+    set_shipping_zone(env, zone, *base_cost);
+
+    Ok(())
+}
+
+// Remove a shipping zone (admin only)
+pub fn remove_zone_rate(env: &Env, admin: &Address, zone: &String) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    if get_shipping_zone(env, zone).is_none() {
+        return Err(AuctionError::NoShippingInfo);
+    }
+    remove_shipping_zone(env, zone);
+
+    Ok(())
+}
+
+// Replace the shipping speed multipliers, quantity tiers and bulk discounts (admin only)
+pub fn set_shipping_rates(
+    env: &Env,
+    admin: &Address,
+    rates: &ShippingRates,
+) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    for (_, multiplier_bps) in rates.speed_multipliers.iter() {
+        if multiplier_bps == 0 {
+            return Err(AuctionError::InvalidConfig);
+        }
+    }
+
+    // Tiers and discounts must be strictly ascending so the last one reached applies
+    let mut previous: Option<u32> = None;
+    for tier in rates.quantity_tiers.iter() {
+        if tier.unit_cost < 0 || previous.is_some_and(|min| tier.min_quantity <= min) {
+            return Err(AuctionError::InvalidConfig);
+        }
+        previous = Some(tier.min_quantity);
+    }
+    let mut previous: Option<u32> = None;
+    for discount in rates.bulk_discounts.iter() {
+        if discount.discount_bps > 10_000
+            || previous.is_some_and(|min| discount.min_quantity <= min)
+        {
+            return Err(AuctionError::InvalidConfig);
+        }
+        previous = Some(discount.min_quantity);
+    }
+
+    env.storage()
         .instance()
-        .get(&StorageKey::Resolvers)
-        .unwrap_or_else(|| Vec::new(env));
-
-    if !resolvers.contains(resolver) {
-        resolvers.push_back(resolver.clone());
-        env.storage()
-            .instance()
-            .set(&StorageKey::Resolvers, &resolvers);
+        .set(&StorageKey::ShippingRates, rates);
+
+    Ok(())
+}
+
+// Set the platform fee applied to auctions created from now on (admin only)
+pub fn set_fee_config(env: &Env, admin: &Address, config: &FeeConfig) -> Result<(), AuctionError> {
+    require_admin(env, admin)?;
+
+    if config.fee_bps > 10_000 || config.flat_fee < 0 {
+        return Err(AuctionError::InvalidFeeConfig);
     }
+
+    env.storage().instance().set(&StorageKey::FeeConfig, config);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "fee_config_updated"),),
+        (config.fee_bps, config.flat_fee, config.recipient.clone()),
+    );
+
+    Ok(())
 }
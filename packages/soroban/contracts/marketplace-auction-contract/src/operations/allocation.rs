@@ -0,0 +1,205 @@
+use super::dispute::{
+    appeal, default_judgment, file_evidence, finalize_ruling, open_case, rule, Sale,
+};
+use super::escrow::settle_allocation;
+use super::storage::{get_auction, get_dispute_window, save_auction};
+use crate::datatype::{
+    Allocation, Auction, AuctionError, AuctionStatus, AuctionType, DisputeResolution,
+    DisputeStatus, SettlementKind,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+// Confirm receipt of the units won in a multi-unit auction (that winner only).
+// Opens the winner's dispute window
+pub fn confirm_allocation_delivery(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<(), AuctionError> {
+    buyer.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+    let (index, mut allocation) = allocation_of(&auction, buyer)?;
+    if allocation.delivered_at != 0 || allocation.dispute_status != DisputeStatus::None {
+        return Err(AuctionError::InvalidShippingTransition);
+    }
+
+    let now = env.ledger().timestamp();
+    allocation.delivered_at = now;
+    allocation.dispute_deadline = now + get_dispute_window(env);
+    auction.allocations.set(index, allocation.clone());
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "allocation_delivered"), auction_id.clone()),
+        (
+            auction_id.clone(),
+            buyer.clone(),
+            allocation.dispute_deadline,
+        ),
+    );
+
+    Ok(())
+}
+
+// Pay the seller for a winner's units once they were received and the dispute
+// window has passed without a dispute. Callable by anyone
+pub fn finalize_allocation(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<(), AuctionError> {
+    let mut auction = get_auction(env, auction_id)?;
+    let (index, mut allocation) = allocation_of(&auction, buyer)?;
+
+    if allocation.settled {
+        return Err(AuctionError::InsufficientEscrow);
+    }
+    if allocation.dispute_status != DisputeStatus::None {
+        return Err(AuctionError::DisputeAlreadyOpen);
+    }
+    if allocation.delivered_at == 0 {
+        return Err(AuctionError::NotDelivered);
+    }
+    if env.ledger().timestamp() <= allocation.dispute_deadline {
+        return Err(AuctionError::DisputeWindowOpen);
+    }
+
+    settle_allocation(env, &mut auction, &allocation, 0, SettlementKind::Sale)?;
+    allocation.settled = true;
+    auction.allocations.set(index, allocation);
+    complete_when_settled(env, &mut auction);
+    save_auction(env, auction_id, &auction);
+
+    Ok(())
+}
+
+// Dispute a winner's units (that winner only). Possible any time before they
+// confirm receipt and until their dispute deadline after. The dispute follows
+// the same case file flow as a single sale, keyed by auction and winner
+pub fn open_allocation_dispute(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    reason: &String,
+) -> Result<(), AuctionError> {
+    buyer.require_auth();
+
+    let auction = get_auction(env, auction_id)?;
+    let (index, allocation) = allocation_of(&auction, buyer)?;
+    if allocation.settled {
+        return Err(AuctionError::DisputeNotAllowed);
+    }
+    if allocation.dispute_status != DisputeStatus::None {
+        return Err(AuctionError::DisputeAlreadyOpen);
+    }
+    if allocation.delivered_at != 0 && env.ledger().timestamp() > allocation.dispute_deadline {
+        return Err(AuctionError::DisputeWindowClosed);
+    }
+
+    let mut sale = Sale {
+        auction,
+        buyer: buyer.clone(),
+        allocation: Some(index),
+    };
+    open_case(env, &mut sale, reason);
+
+    Ok(())
+}
+
+// Add evidence to a winner's open or appealed allocation dispute (that winner or the seller)
+pub fn submit_allocation_evidence(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    party: &Address,
+    text: &String,
+    content_hashes: &Vec<BytesN<32>>,
+) -> Result<(), AuctionError> {
+    let sale = Sale::allocation(env, auction_id, buyer)?;
+    file_evidence(env, &sale, party, text, content_hashes)
+}
+
+// Rule on a winner's allocation dispute (admin or dispute resolver only), as
+// resolve_dispute does for a single sale. Settling it pays out only that winner's payment
+pub fn resolve_allocation_dispute(
+    env: &Env,
+    resolver: &Address,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    resolution: &DisputeResolution,
+) -> Result<(), AuctionError> {
+    rule(
+        env,
+        Sale::allocation(env, auction_id, buyer)?,
+        resolver,
+        resolution,
+    )
+}
+
+// Appeal the ruling on a winner's allocation dispute (that winner or the seller, once)
+pub fn appeal_allocation_dispute(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    party: &Address,
+) -> Result<(), AuctionError> {
+    appeal(env, Sale::allocation(env, auction_id, buyer)?, party)
+}
+
+// Settle an unappealed ruling on a winner's allocation once the appeal deadline has passed (anyone)
+pub fn finalize_allocation_dispute(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<(), AuctionError> {
+    finalize_ruling(env, Sale::allocation(env, auction_id, buyer)?)
+}
+
+// Refund a winner whose allocation dispute the seller did not answer in time (anyone)
+pub fn allocation_default_judgment(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<(), AuctionError> {
+    default_judgment(env, Sale::allocation(env, auction_id, buyer)?)
+}
+
+// Position and record of a winner's allocation in a cleared multi-unit auction
+pub(super) fn allocation_of(
+    auction: &Auction,
+    buyer: &Address,
+) -> Result<(u32, Allocation), AuctionError> {
+    if auction.options.auction_type != AuctionType::MultiUnit {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    if auction.status != AuctionStatus::Ended {
+        return Err(AuctionError::AuctionNotSold);
+    }
+
+    for (index, allocation) in auction.allocations.iter().enumerate() {
+        if allocation.bidder == *buyer {
+            return Ok((index as u32, allocation));
+        }
+    }
+    Err(AuctionError::NotHighestBidder)
+}
+
+// Complete the auction once every winner's payment has been paid out
+pub(super) fn complete_when_settled(env: &Env, auction: &mut Auction) {
+    if !auction
+        .allocations
+        .iter()
+        .all(|allocation| allocation.settled)
+    {
+        return;
+    }
+    auction.status = AuctionStatus::Completed;
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "auction_completed"), auction.id.clone()),
+        auction.id.clone(),
+    );
+}
@@ -1,6 +1,13 @@
-use super::storage::{add_to_user_selling, get_and_increment_auction_counter, save_auction};
-use crate::datatype::{Auction, AuctionStatus, DisputeStatus, Product, ProductCondition};
-use crate::ShippingStatus;
+use super::expire_auction;
+use super::storage::{
+    add_to_product_lineage, add_to_user_selling, get_and_increment_auction_counter, get_auction,
+    get_fee_config, get_lot, get_payment_token, get_product_lineage, save_auction, save_lot,
+    set_auction_index,
+};
+use crate::datatype::{
+    Auction, AuctionError, AuctionOptions, AuctionStatus, AuctionType, DutchSchedule, Product,
+    ProductCondition, ShippingStatus,
+};
 use soroban_sdk::{Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
 pub fn create_auction(
@@ -14,35 +21,23 @@ pub fn create_auction(
     reserve_price: &i128,
     start_time: &u64,
     end_time: &u64,
-) -> BytesN<32> {
+    options: &AuctionOptions,
+) -> Result<BytesN<32>, AuctionError> {
     seller.require_auth();
 
-    // Validate inputs
-    if start_time >= end_time {
-        panic!("End time must be after start time");
-    }
-    if *inventory_count == 0 {
-        panic!("Inventory count must be greater than 0");
-    }
-    if *reserve_price <= 0 {
-        panic!("Reserve price must be greater than 0");
-    }
+    validate_listing(
+        *inventory_count,
+        *reserve_price,
+        *start_time,
+        *end_time,
+        options,
+    )?;
 
-    // Generate counter for unique ID
-    let counter = get_and_increment_auction_counter(env);
-
-    // Create auction ID by hashing counter and timestamp bytes
-    let counter_bytes = Bytes::from_slice(env, &counter.to_be_bytes());
-    let auction_id: BytesN<32> = env.crypto().sha256(&counter_bytes).into();
-
-    // Create product ID using a different input
-    let product_counter = counter + 1;
-    let product_bytes = Bytes::from_slice(env, &product_counter.to_be_bytes());
-    let product_id: BytesN<32> = env.crypto().sha256(&product_bytes).into();
+    let (counter, auction_id) = next_auction_id(env);
 
     // Create product
     let product = Product {
-        id: product_id,
+        id: product_id(env, counter),
         name: name.clone(),
         description: description.clone(),
         condition: *condition,
@@ -50,37 +45,130 @@ pub fn create_auction(
         seller: seller.clone(),
         inventory_count: *inventory_count,
         is_authenticated: false,
+        attestations: Vec::new(env),
     };
 
-    // Create auction with Option<T> fields
-    let auction = Auction {
-        id: auction_id.clone(),
+    // Create auction, snapshotting the payment token configured at initialization
+    let auction = Auction::new(
+        env,
+        auction_id,
         product,
-        status: AuctionStatus::Pending,
-        start_time: *start_time,
-        end_time: *end_time,
-        reserve_price: *reserve_price,
-        has_highest_bid: false,
-        highest_bidder: env.current_contract_address(),
-        highest_bid_amount: 0,
-        highest_bid_timestamp: 0,
-        highest_bid_quantity: 0,
-        all_bids: Vec::new(env),
-        has_shipping: false,
-        shipping_status: ShippingStatus::NotShipped,
-        shipping_tracking: String::from_str(env, ""),
-        shipping_carrier: String::from_str(env, ""),
-        shipping_delivery_estimate: 0,
-        shipping_cost: 0,
-        shipping_recipient: String::from_str(env, ""),
-        dispute_status: DisputeStatus::None,
-        has_dispute_reason: false,
-        dispute_reason: String::from_str(env, ""),
+        *start_time,
+        *end_time,
+        *reserve_price,
+        get_payment_token(env)?,
+        get_fee_config(env),
+        options.clone(),
+    );
+    Ok(list_auction(env, counter, &auction))
+}
+
+// Relist the product of an unsold, expired or cancelled auction as a new auction
+// linked to the original. A reserve price of 0 keeps the original reserve
+pub fn relist_auction(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    start_time: &u64,
+    end_time: &u64,
+    reserve_price: &i128,
+) -> Result<BytesN<32>, AuctionError> {
+    let mut original = get_auction(env, auction_id)?;
+
+    // Only the seller can relist
+    original.product.seller.require_auth();
+
+    // A Pending auction whose end time has passed expires first
+    if original.status == AuctionStatus::Pending && env.ledger().timestamp() >= original.end_time {
+        expire_auction(env, &mut original);
+    }
+
+    match original.status {
+        AuctionStatus::ReserveNotMet | AuctionStatus::Expired | AuctionStatus::Cancelled => {}
+        _ => return Err(AuctionError::AuctionNotEnded),
+    }
+
+    // Only the latest listing of a product can be relisted
+    let lineage = get_product_lineage(env, &original.product.id);
+    if lineage.last() != Some(auction_id.clone()) {
+        return Err(AuctionError::AuctionNotEnded);
+    }
+
+    let reserve_price = if *reserve_price == 0 {
+        original.reserve_price
+    } else {
+        *reserve_price
     };
+    validate_listing(
+        original.product.inventory_count,
+        reserve_price,
+        *start_time,
+        *end_time,
+        &original.options,
+    )?;
 
-    // Save the auction
-    save_auction(env, &auction_id, &auction);
-    add_to_user_selling(env, seller, &auction_id);
+    let (counter, new_id) = next_auction_id(env);
+
+    // Same product and options; fee and payment token are those currently in force
+    let mut auction = Auction::new(
+        env,
+        new_id.clone(),
+        original.product.clone(),
+        *start_time,
+        *end_time,
+        reserve_price,
+        get_payment_token(env)?,
+        get_fee_config(env),
+        original.options.clone(),
+    );
+    auction.has_relisted_from = true;
+    auction.relisted_from = auction_id.clone();
+
+    // A relisted lot starts again with every item unshipped
+    if original.lot_size > 0 {
+        let mut lot = get_lot(env, auction_id);
+        for index in 0..lot.len() {
+            let mut item = lot.get(index).unwrap();
+            item.shipping_status = ShippingStatus::NotShipped;
+            item.delivered_at = 0;
+            lot.set(index, item);
+        }
+        save_lot(env, &new_id, &lot);
+        auction.lot_size = original.lot_size;
+    }
+    list_auction(env, counter, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "auction_relisted"), auction_id.clone()),
+        (auction_id.clone(), new_id.clone()),
+    );
+
+    Ok(new_id)
+}
+
+// Take the next auction counter value and derive the auction ID from it
+pub(super) fn next_auction_id(env: &Env) -> (u32, BytesN<32>) {
+    let counter = get_and_increment_auction_counter(env);
+
+    // Create auction ID by hashing counter bytes
+    let counter_bytes = Bytes::from_slice(env, &counter.to_be_bytes());
+    (counter, env.crypto().sha256(&counter_bytes).into())
+}
+
+// Create product ID using a different input than the auction ID
+pub(super) fn product_id(env: &Env, counter: u32) -> BytesN<32> {
+    let product_counter = counter + 1;
+    let product_bytes = Bytes::from_slice(env, &product_counter.to_be_bytes());
+    env.crypto().sha256(&product_bytes).into()
+}
+
+// Save a new auction and register it for discovery queries
+pub(super) fn list_auction(env: &Env, counter: u32, auction: &Auction) -> BytesN<32> {
+    let auction_id = auction.id.clone();
+    save_auction(env, &auction_id, auction);
+    set_auction_index(env, counter, &auction_id);
+    add_to_user_selling(env, &auction.product.seller, &auction_id);
+    add_to_product_lineage(env, &auction.product.id, &auction_id);
 
     // Emit event
     env.events().publish(
@@ -90,3 +178,97 @@ pub fn create_auction(
 
     auction_id
 }
+
+// Validate listing times, inventory, reserve and options
+pub(super) fn validate_listing(
+    inventory_count: u32,
+    reserve_price: i128,
+    start_time: u64,
+    end_time: u64,
+    options: &AuctionOptions,
+) -> Result<(), AuctionError> {
+    if start_time >= end_time {
+        return Err(AuctionError::InvalidTimeRange);
+    }
+    if inventory_count == 0 {
+        return Err(AuctionError::InvalidInventory);
+    }
+    if reserve_price <= 0 {
+        return Err(AuctionError::InvalidReservePrice);
+    }
+    if options.extension_window > 0
+        && (options.extension_duration == 0 || options.max_extension == 0)
+    {
+        return Err(AuctionError::InvalidSoftClose);
+    }
+    // The latest extended end time must fit in a timestamp
+    if end_time.checked_add(options.max_extension).is_none() {
+        return Err(AuctionError::InvalidSoftClose);
+    }
+    match &options.auction_type {
+        AuctionType::English | AuctionType::MultiUnit => {}
+        // Service requests are posted with create_service_request
+        AuctionType::Reverse => return Err(AuctionError::UnsupportedAuctionType),
+        AuctionType::Dutch(schedule) => validate_dutch_schedule(schedule, reserve_price)?,
+        AuctionType::Sealed(config) => {
+            if config.reveal_duration == 0 {
+                return Err(AuctionError::InvalidSealedConfig);
+            }
+            if config.deposit <= 0 {
+                return Err(AuctionError::InvalidSealedConfig);
+            }
+        }
+    }
+    let supports_soft_close = matches!(
+        options.auction_type,
+        AuctionType::English | AuctionType::MultiUnit
+    );
+    if options.extension_window > 0 && !supports_soft_close {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    if options.bid_increment < 0 {
+        return Err(AuctionError::InvalidBidIncrement);
+    }
+    if options.bid_increment_bps > 10_000 {
+        return Err(AuctionError::InvalidBidIncrement);
+    }
+    if options.starting_price != 0 {
+        if options.auction_type != AuctionType::English {
+            return Err(AuctionError::UnsupportedAuctionType);
+        }
+        if options.starting_price < 0 || options.starting_price > reserve_price {
+            return Err(AuctionError::InvalidReservePrice);
+        }
+    }
+    if options.buy_now_price != 0 {
+        if options.auction_type != AuctionType::English {
+            return Err(AuctionError::UnsupportedAuctionType);
+        }
+        if options.buy_now_price < reserve_price {
+            return Err(AuctionError::InvalidBuyNow);
+        }
+        if options.buy_now_threshold < 0 || options.buy_now_threshold >= options.buy_now_price {
+            return Err(AuctionError::InvalidBuyNow);
+        }
+    }
+
+    Ok(())
+}
+
+// Validate a Dutch auction price schedule against the reserve price
+fn validate_dutch_schedule(
+    schedule: &DutchSchedule,
+    reserve_price: i128,
+) -> Result<(), AuctionError> {
+    if schedule.floor_price < reserve_price {
+        return Err(AuctionError::InvalidDutchSchedule);
+    }
+    if schedule.start_price < schedule.floor_price {
+        return Err(AuctionError::InvalidDutchSchedule);
+    }
+    if schedule.price_decrement <= 0 || schedule.decrement_interval == 0 {
+        return Err(AuctionError::InvalidDutchSchedule);
+    }
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
-use super::storage::{get_auction, save_auction};
-use crate::datatype::{AuctionStatus, Bid};
+use super::escrow::collect;
+use super::proxy::{leading_proxy, raise_proxy_leader, raise_proxy_max, release_leader};
+use super::storage::{append_bid, get_auction, save_auction};
+use super::{close_auction, refresh_authentication};
+use crate::datatype::{Auction, AuctionError, AuctionStatus, AuctionType, Bid};
 use soroban_sdk::{Address, BytesN, Env, Symbol};
 
 // Place a bid on an auction
@@ -9,73 +12,289 @@ pub fn place_bid(
     bidder: &Address,
     amount: &i128,
     quantity: &u32,
-) {
+) -> Result<(), AuctionError> {
     bidder.require_auth();
 
-    let mut auction = get_auction(env, auction_id);
+    let mut auction = get_auction(env, auction_id)?;
 
     // Validate auction state
-    if auction.status != AuctionStatus::Active {
-        panic!("Auction is not active");
+    let is_multi_unit = match auction.options.auction_type {
+        AuctionType::English => false,
+        AuctionType::MultiUnit => true,
+        _ => return Err(AuctionError::UnsupportedAuctionType),
+    };
+    let current_time = validate_bidding_open(env, &auction)?;
+
+    // Check inventory
+    if *quantity > auction.product.inventory_count {
+        return Err(AuctionError::InvalidQuantity);
     }
 
-    // Validate auction state
-    if auction.status != AuctionStatus::Active {
-        panic!("Auction is not active");
+    // Check bid amount
+    if *amount <= 0 {
+        return Err(AuctionError::InvalidAmount);
     }
 
-    // Validate auction state
+    // Create new bid
+    let new_bid = Bid {
+        bidder: bidder.clone(),
+        amount: *amount,
+        timestamp: current_time,
+        quantity: *quantity,
+    };
+
+    let previous_bid = auction.highest_bid();
+    let mut raised_own_max = false;
+    if is_multi_unit {
+        // Every multi-unit bid is kept; the amount is a unit price
+        if *quantity == 0 {
+            return Err(AuctionError::InvalidQuantity);
+        }
+        if *amount < auction.reserve_price {
+            return Err(AuctionError::BidTooLow);
+        }
+        collect(env, &mut auction, bidder, *amount * *quantity as i128)?;
+
+        let is_highest = match &previous_bid {
+            Some(highest_bid) => new_bid.amount > highest_bid.amount,
+            None => true,
+        };
+        if is_highest {
+            auction.set_highest_bid(Some(new_bid.clone()));
+        }
+        append_bid(env, &mut auction, &new_bid);
+    } else {
+        match leading_proxy(env, &auction) {
+            // The leader does not bid against their own proxy; the bid
+            // becomes their new maximum instead
+            Some(proxy) if proxy.bidder == *bidder => {
+                raise_proxy_max(env, &mut auction, &proxy, *amount)?;
+                raised_own_max = true;
+            }
+            _ if *amount < min_next_bid(&auction) => return Err(AuctionError::BidTooLow),
+            // A leading proxy bid answers automatically up to its maximum,
+            // keeping the lead on ties; this bid is recorded but does not lead
+            Some(proxy) if proxy.max_amount >= *amount => {
+                append_bid(env, &mut auction, &new_bid);
+                raise_proxy_leader(env, &mut auction, &proxy, *amount);
+            }
+            // Move the bid into escrow, then refund the bidder that was outbid
+            _ => {
+                collect(env, &mut auction, bidder, *amount)?;
+                release_leader(env, &mut auction)?;
+                auction.set_highest_bid(Some(new_bid.clone()));
+                append_bid(env, &mut auction, &new_bid);
+            }
+        }
+    }
+
+    // Soft close: a late bid pushes the deadline out, up to the configured cap
+    apply_soft_close(env, &mut auction, current_time);
+    save_auction(env, auction_id, &auction);
+
+    // Emit event (a raised maximum stays hidden)
+    if raised_own_max {
+        env.events().publish(
+            (Symbol::new(env, "proxy_bid_placed"), auction_id.clone()),
+            (
+                auction_id.clone(),
+                bidder.clone(),
+                auction.highest_bid_amount,
+            ),
+        );
+    } else {
+        env.events().publish(
+            (Symbol::new(env, "bid_placed"), auction_id.clone()),
+            (auction_id.clone(), bidder.clone(), *amount, *quantity),
+        );
+    }
+
+    Ok(())
+}
+
+// Check that an auction is accepting bids, returning the current time
+pub(super) fn validate_bidding_open(env: &Env, auction: &Auction) -> Result<u64, AuctionError> {
     if auction.status != AuctionStatus::Active {
-        panic!("Auction is not active");
+        return Err(AuctionError::AuctionNotActive);
     }
+    require_authenticated(env, auction)?;
 
     let current_time = env.ledger().timestamp();
     if current_time < auction.start_time {
-        panic!("Auction has not started yet");
+        return Err(AuctionError::AuctionNotStarted);
     }
     if current_time > auction.end_time {
-        panic!("Auction has already ended");
+        return Err(AuctionError::AuctionAlreadyEnded);
     }
 
-    // Check inventory
-    if *quantity > auction.product.inventory_count {
-        panic!("Requested quantity exceeds available inventory");
+    Ok(current_time)
+}
+
+// Reject bids on a product awaiting authentication when the seller asked for it
+// against the current verifiers and quorum
+pub(super) fn require_authenticated(env: &Env, auction: &Auction) -> Result<(), AuctionError> {
+    if !auction.options.require_authentication {
+        return Ok(());
     }
 
-    // Check bid amount
+    let mut auction = auction.clone();
+    refresh_authentication(env, &mut auction);
+    if !auction.product.is_authenticated {
+        return Err(AuctionError::AuctionNotActive);
+    }
+
+    Ok(())
+}
+
+// Smallest raise over `amount` allowed by the auction's increment settings
+pub(super) fn bid_step(auction: &Auction, amount: i128) -> i128 {
+    let relative = amount * auction.options.bid_increment_bps as i128 / 10_000;
+    auction.options.bid_increment.max(relative).max(1)
+}
+
+// Lowest amount the next English bid may be. The opening bid must meet the
+// starting price, or the reserve price when none is set; later bids raise
+// the highest bid by one increment
+pub(super) fn min_next_bid(auction: &Auction) -> i128 {
     if auction.has_highest_bid {
-        let highest_bid = Bid {
-            bidder: auction.highest_bidder.clone(),
-            amount: auction.highest_bid_amount,
-            timestamp: auction.highest_bid_timestamp,
-            quantity: auction.highest_bid_quantity,
-        };
+        auction.highest_bid_amount + bid_step(auction, auction.highest_bid_amount)
+    } else if auction.options.starting_price > 0 {
+        auction.options.starting_price
+    } else {
+        auction.reserve_price
+    }
+}
 
-        if amount <= &highest_bid.amount {
-            panic!("Bid amount must be higher than current highest bid");
-        }
+// Extend the auction end time if a bid lands inside the soft-close window
+pub(super) fn apply_soft_close(env: &Env, auction: &mut Auction, current_time: u64) {
+    let window = auction.options.extension_window;
+    if window == 0 || current_time.saturating_add(window) < auction.end_time {
+        return;
     }
 
-    // Create new bid
-    let new_bid = Bid {
-        bidder: bidder.clone(),
-        amount: *amount,
-        timestamp: env.ledger().timestamp(),
+    // Saturate rather than overflow: the end is capped at max_end_time anyway
+    let new_end_time = auction
+        .end_time
+        .saturating_add(auction.options.extension_duration)
+        .min(auction.max_end_time);
+    if new_end_time <= auction.end_time {
+        return;
+    }
+
+    auction.end_time = new_end_time;
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "auction_extended"), auction.id.clone()),
+        (auction.id.clone(), auction.end_time),
+    );
+}
+
+// Buy at the current Dutch auction price, ending the auction immediately
+pub fn accept_price(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    quantity: &u32,
+) -> Result<(), AuctionError> {
+    buyer.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+
+    if auction.status != AuctionStatus::Active {
+        return Err(AuctionError::AuctionNotActive);
+    }
+    require_authenticated(env, &auction)?;
+
+    let current_time = env.ledger().timestamp();
+    if current_time > auction.end_time {
+        return Err(AuctionError::AuctionAlreadyEnded);
+    }
+
+    let schedule = match &auction.options.auction_type {
+        AuctionType::Dutch(schedule) => schedule.clone(),
+        _ => return Err(AuctionError::UnsupportedAuctionType),
+    };
+
+    if *quantity == 0 || *quantity > auction.product.inventory_count {
+        return Err(AuctionError::InvalidQuantity);
+    }
+
+    // Pay the current unit price for the requested quantity
+    let unit_price = schedule.price_at(auction.start_time, current_time);
+    let amount = unit_price * *quantity as i128;
+    collect(env, &mut auction, buyer, amount)?;
+
+    let winning_bid = Bid {
+        bidder: buyer.clone(),
+        amount,
+        timestamp: current_time,
         quantity: *quantity,
     };
+    auction.set_highest_bid(Some(winning_bid.clone()));
+    append_bid(env, &mut auction, &winning_bid);
 
-    // Update auction
-    auction.has_highest_bid = true;
-    auction.highest_bidder = new_bid.bidder.clone();
-    auction.highest_bid_amount = new_bid.amount;
-    auction.highest_bid_timestamp = new_bid.timestamp;
-    auction.highest_bid_quantity = new_bid.quantity;
-    auction.all_bids.push_back(new_bid);
-    save_auction(env, auction_id, &auction);
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "price_accepted"), auction_id.clone()),
+        (auction_id.clone(), buyer.clone(), unit_price, *quantity),
+    );
+
+    // The first buyer wins outright
+    close_auction(env, &mut auction);
+
+    Ok(())
+}
+
+// Pay the buy-it-now price, ending the auction immediately
+pub fn buy_now(env: &Env, auction_id: &BytesN<32>, buyer: &Address) -> Result<(), AuctionError> {
+    buyer.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+
+    if auction.status != AuctionStatus::Active {
+        return Err(AuctionError::AuctionNotActive);
+    }
+    require_authenticated(env, &auction)?;
+
+    let current_time = env.ledger().timestamp();
+    if current_time > auction.end_time {
+        return Err(AuctionError::AuctionAlreadyEnded);
+    }
+
+    let price = auction.options.buy_now_price;
+    if price == 0 {
+        return Err(AuctionError::BuyNowUnavailable);
+    }
+
+    // Buy now closes once bidding passes the threshold
+    let previous_bid = auction.highest_bid();
+    if let Some(highest_bid) = &previous_bid {
+        if highest_bid.amount > auction.options.buy_now_threshold {
+            return Err(AuctionError::BuyNowThresholdExceeded);
+        }
+    }
+
+    // Escrow the price and refund the current highest bidder
+    collect(env, &mut auction, buyer, price)?;
+    release_leader(env, &mut auction)?;
+
+    let winning_bid = Bid {
+        bidder: buyer.clone(),
+        amount: price,
+        timestamp: current_time,
+        quantity: auction.product.inventory_count,
+    };
+    auction.set_highest_bid(Some(winning_bid.clone()));
+    append_bid(env, &mut auction, &winning_bid);
 
     // Emit event
     env.events().publish(
-        (Symbol::new(env, "bid_placed"), auction_id.clone()),
-        (auction_id.clone(), bidder.clone(), *amount, *quantity),
+        (Symbol::new(env, "bought_now"), auction_id.clone()),
+        (auction_id.clone(), buyer.clone(), price),
     );
+
+    close_auction(env, &mut auction);
+
+    Ok(())
 }
@@ -0,0 +1,105 @@
+use super::escrow::refund;
+use super::storage::get_all_bids;
+use crate::datatype::{Allocation, Auction, AuctionError, DisputeStatus};
+use soroban_sdk::{Env, Symbol, Vec};
+
+// Clear a multi-unit auction: allocate inventory to the highest unit prices
+// and charge every winner the same clearing price
+pub fn clear_multi_unit(env: &Env, auction: &mut Auction) -> Result<(), AuctionError> {
+    let bids = get_all_bids(env, auction);
+
+    // Rank bids by unit price; bids are stored in time order, so earlier bids
+    // stay ahead on equal prices
+    let mut ranking: Vec<u32> = Vec::new(env);
+    for index in 0..bids.len() {
+        let amount = bids.get(index).unwrap().amount;
+        let mut position = ranking.len();
+        while position > 0 {
+            let ahead = bids.get(ranking.get(position - 1).unwrap()).unwrap();
+            if ahead.amount >= amount {
+                break;
+            }
+            position -= 1;
+        }
+        ranking.insert(position, index);
+    }
+
+    // Walk down the ranking until inventory runs out; the last bid filled
+    // sets the clearing price
+    let mut filled: Vec<u32> = Vec::new(env);
+    for _ in 0..bids.len() {
+        filled.push_back(0);
+    }
+    let mut remaining = auction.product.inventory_count;
+    let mut clearing_price: i128 = 0;
+    for index in ranking.iter() {
+        if remaining == 0 {
+            break;
+        }
+        let bid = bids.get(index).unwrap();
+        let quantity = bid.quantity.min(remaining);
+        filled.set(index, quantity);
+        remaining -= quantity;
+        clearing_price = bid.amount;
+    }
+
+    // Refund what each bid escrowed beyond what it owes at the clearing price
+    let mut allocations: Vec<Allocation> = Vec::new(env);
+    for index in 0..bids.len() {
+        let bid = bids.get(index).unwrap();
+        let quantity = filled.get(index).unwrap();
+        let owed = clearing_price * quantity as i128;
+        refund(
+            env,
+            auction,
+            &bid.bidder,
+            bid.amount * bid.quantity as i128 - owed,
+        )?;
+
+        if quantity > 0 {
+            add_allocation(&mut allocations, &bid.bidder, quantity, clearing_price);
+        }
+    }
+
+    auction.clearing_price = clearing_price;
+    auction.allocations = allocations;
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "auction_cleared"), auction.id.clone()),
+        (
+            auction.id.clone(),
+            clearing_price,
+            auction.product.inventory_count - remaining,
+        ),
+    );
+
+    Ok(())
+}
+
+// Add units to a bidder's allocation, merging multiple bids from the same bidder
+fn add_allocation(
+    allocations: &mut Vec<Allocation>,
+    bidder: &soroban_sdk::Address,
+    quantity: u32,
+    unit_price: i128,
+) {
+    for index in 0..allocations.len() {
+        let mut allocation = allocations.get(index).unwrap();
+        if allocation.bidder == *bidder {
+            allocation.quantity += quantity;
+            allocations.set(index, allocation);
+            return;
+        }
+    }
+
+    allocations.push_back(Allocation {
+        bidder: bidder.clone(),
+        quantity,
+        unit_price,
+        delivered_at: 0,
+        dispute_deadline: 0,
+        dispute_status: DisputeStatus::None,
+        settled: false,
+    });
+}
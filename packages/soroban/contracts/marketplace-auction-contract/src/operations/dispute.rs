@@ -1,78 +1,453 @@
-use super::storage::{get_admin, get_auction, is_resolver, save_auction};
-use crate::datatype::{AuctionStatus, DisputeStatus};
-use soroban_sdk::{Address, BytesN, Env, String, Symbol};
+use super::allocation::{allocation_of, complete_when_settled};
+use super::escrow::{settle_allocation, settle_dispute};
+use super::storage::{
+    get_admin, get_appeal_window, get_auction, get_dispute_case, get_response_window, is_resolver,
+    save_auction, save_dispute_case,
+};
+use crate::datatype::{
+    Auction, AuctionError, AuctionStatus, AuctionType, DisputeCase, DisputeResolution,
+    DisputeStatus, Evidence, Ruling, SettlementKind, ShippingStatus,
+};
+use soroban_sdk::{Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
+
+// Upper bound on evidence entries each party can add to a case file, so
+// neither side can use up the other's room to respond
+const MAX_EVIDENCE_PER_PARTY: u32 = 10;
+
+// A purchase under dispute: the auction's single sale, or one winner's
+// allocation on a multi-unit auction. Both go through the same case file flow
+pub(super) struct Sale {
+    pub auction: Auction,
+    pub buyer: Address,
+    pub allocation: Option<u32>,
+}
+
+impl Sale {
+    // The single sale of an auction, bought by its highest bidder
+    fn single(env: &Env, auction_id: &BytesN<32>) -> Result<Self, AuctionError> {
+        let auction = get_auction(env, auction_id)?;
+        let buyer = auction.highest_bidder.clone();
+        Ok(Sale {
+            auction,
+            buyer,
+            allocation: None,
+        })
+    }
+
+    // One winner's allocation on a cleared multi-unit auction
+    pub fn allocation(
+        env: &Env,
+        auction_id: &BytesN<32>,
+        buyer: &Address,
+    ) -> Result<Self, AuctionError> {
+        let auction = get_auction(env, auction_id)?;
+        let (index, _) = allocation_of(&auction, buyer)?;
+        Ok(Sale {
+            auction,
+            buyer: buyer.clone(),
+            allocation: Some(index),
+        })
+    }
+
+    fn dispute_status(&self) -> DisputeStatus {
+        match self.allocation {
+            Some(index) => self.auction.allocations.get(index).unwrap().dispute_status,
+            None => self.auction.dispute_status,
+        }
+    }
+
+    fn set_dispute_status(&mut self, status: DisputeStatus) {
+        match self.allocation {
+            Some(index) => {
+                let mut allocation = self.auction.allocations.get(index).unwrap();
+                allocation.dispute_status = status;
+                self.auction.allocations.set(index, allocation);
+            }
+            None => self.auction.dispute_status = status,
+        }
+    }
+
+    fn case(&self, env: &Env) -> Result<DisputeCase, AuctionError> {
+        get_dispute_case(env, &self.auction.id, &self.buyer)
+    }
+
+    fn save(&self, env: &Env) {
+        save_auction(env, &self.auction.id, &self.auction);
+    }
+
+    // Emit a dispute event; allocation disputes carry the buyer as an extra topic
+    fn publish<D: IntoVal<Env, Val>>(&self, env: &Env, name: &str, data: D) {
+        let topic = Symbol::new(env, name);
+        match self.allocation {
+            Some(_) => env
+                .events()
+                .publish((topic, self.auction.id.clone(), self.buyer.clone()), data),
+            None => env.events().publish((topic, self.auction.id.clone()), data),
+        }
+    }
+}
 
 // Open a dispute
-pub fn open_dispute(env: &Env, auction_id: &BytesN<32>, buyer: &Address, reason: &String) {
+pub fn open_dispute(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    reason: &String,
+) -> Result<(), AuctionError> {
     buyer.require_auth();
 
-    let mut auction = get_auction(env, auction_id);
+    let mut auction = get_auction(env, auction_id)?;
+
+    // Multi-unit winners dispute their own allocation
+    if auction.options.auction_type == AuctionType::MultiUnit {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
 
     // Check if caller is highest bidder
     if auction.has_highest_bid {
         if auction.highest_bidder != *buyer {
-            panic!("Only the highest bidder can open a dispute");
+            return Err(AuctionError::NotHighestBidder);
         }
     } else {
-        panic!("No bids on this auction");
+        return Err(AuctionError::AuctionNotSold);
     }
 
     // Validate auction status
     if auction.status != AuctionStatus::Ended && auction.status != AuctionStatus::Completed {
-        panic!("Can only open disputes for ended or completed auctions");
+        return Err(AuctionError::DisputeNotAllowed);
     }
 
     if auction.dispute_status != DisputeStatus::None {
-        panic!("A dispute is already open for this auction");
+        return Err(AuctionError::DisputeAlreadyOpen);
+    }
+
+    // Once delivered, the buyer only has until the dispute deadline
+    if auction.shipping_status == ShippingStatus::Delivered
+        && env.ledger().timestamp() > auction.dispute_deadline
+    {
+        return Err(AuctionError::DisputeWindowClosed);
     }
 
     // Update auction
-    auction.dispute_status = DisputeStatus::Open;
     auction.has_dispute_reason = true;
     auction.dispute_reason = reason.clone();
     auction.status = AuctionStatus::Disputed;
-    save_auction(env, auction_id, &auction);
+
+    let mut sale = Sale {
+        auction,
+        buyer: buyer.clone(),
+        allocation: None,
+    };
+    open_case(env, &mut sale, reason);
+
+    Ok(())
+}
+
+// Start the case file of a sale and mark its dispute Open. The seller has
+// until the response deadline to answer
+pub(super) fn open_case(env: &Env, sale: &mut Sale, reason: &String) {
+    let current_time = env.ledger().timestamp();
+    let case = DisputeCase {
+        buyer: sale.buyer.clone(),
+        seller: sale.auction.product.seller.clone(),
+        reason: reason.clone(),
+        opened_at: current_time,
+        response_deadline: current_time + get_response_window(env),
+        seller_responded: false,
+        evidence: Vec::new(env),
+        rulings: Vec::new(env),
+        appeal_deadline: 0,
+    };
+    save_dispute_case(env, &sale.auction.id, &case);
+
+    sale.set_dispute_status(DisputeStatus::Open);
+    sale.save(env);
 
     // Emit event
-    env.events().publish(
-        (Symbol::new(env, "dispute_opened"), auction_id.clone()),
-        (auction_id.clone(), reason.clone()),
+    sale.publish(
+        env,
+        "dispute_opened",
+        (sale.auction.id.clone(), reason.clone()),
     );
 }
 
-// Resolve a dispute
+// Add evidence to an open or appealed dispute (buyer or seller)
+pub fn submit_evidence(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    party: &Address,
+    text: &String,
+    content_hashes: &Vec<BytesN<32>>,
+) -> Result<(), AuctionError> {
+    file_evidence(
+        env,
+        &Sale::single(env, auction_id)?,
+        party,
+        text,
+        content_hashes,
+    )
+}
+
+pub(super) fn file_evidence(
+    env: &Env,
+    sale: &Sale,
+    party: &Address,
+    text: &String,
+    content_hashes: &Vec<BytesN<32>>,
+) -> Result<(), AuctionError> {
+    party.require_auth();
+
+    if sale.dispute_status() != DisputeStatus::Open
+        && sale.dispute_status() != DisputeStatus::Appealed
+    {
+        return Err(AuctionError::NoOpenDispute);
+    }
+
+    let mut case = sale.case(env)?;
+    if *party != case.buyer && *party != case.seller {
+        return Err(AuctionError::Unauthorized);
+    }
+    let filed = case
+        .evidence
+        .iter()
+        .filter(|entry| entry.submitter == *party)
+        .count() as u32;
+    if filed >= MAX_EVIDENCE_PER_PARTY {
+        return Err(AuctionError::EvidenceLimitReached);
+    }
+
+    // Seller evidence counts as a response only before the deadline; later
+    // submissions are kept on file but do not stop a default judgment
+    let current_time = env.ledger().timestamp();
+    if *party == case.seller && current_time <= case.response_deadline {
+        case.seller_responded = true;
+    }
+
+    case.evidence.push_back(Evidence {
+        submitter: party.clone(),
+        text: text.clone(),
+        content_hashes: content_hashes.clone(),
+        timestamp: current_time,
+    });
+    save_dispute_case(env, &sale.auction.id, &case);
+
+    // Emit event
+    sale.publish(
+        env,
+        "evidence_submitted",
+        (sale.auction.id.clone(), party.clone(), case.evidence.len()),
+    );
+
+    Ok(())
+}
+
+// Rule on a dispute (admin or dispute resolver only). A first ruling can be
+// appealed until the appeal deadline; a ruling on appeal settles immediately.
 pub fn resolve_dispute(
     env: &Env,
     resolver: &Address,
     auction_id: &BytesN<32>,
-    resolution: &DisputeStatus,
-) {
+    resolution: &DisputeResolution,
+) -> Result<(), AuctionError> {
+    rule(env, Sale::single(env, auction_id)?, resolver, resolution)
+}
+
+pub(super) fn rule(
+    env: &Env,
+    mut sale: Sale,
+    resolver: &Address,
+    resolution: &DisputeResolution,
+) -> Result<(), AuctionError> {
     resolver.require_auth();
 
     // Check authorization
-    let admin = get_admin(env);
+    let admin = get_admin(env)?;
     if *resolver != admin && !is_resolver(env, resolver) {
-        panic!("Not authorized to resolve disputes");
+        return Err(AuctionError::Unauthorized);
     }
 
-    if *resolution == DisputeStatus::None || *resolution == DisputeStatus::Open {
-        panic!("Invalid resolution status");
+    resolution_outcome(resolution)?;
+
+    let current_time = env.ledger().timestamp();
+    let ruling = Ruling {
+        resolver: resolver.clone(),
+        resolution: *resolution,
+        timestamp: current_time,
+    };
+
+    match sale.dispute_status() {
+        DisputeStatus::Open => {
+            let mut case = sale.case(env)?;
+            if !case.seller_responded {
+                return Err(AuctionError::SellerResponsePending);
+            }
+
+            case.rulings.push_back(ruling);
+            case.appeal_deadline = current_time + get_appeal_window(env);
+            save_dispute_case(env, &sale.auction.id, &case);
+
+            sale.set_dispute_status(DisputeStatus::Ruled);
+            sale.save(env);
+
+            // Emit event
+            sale.publish(
+                env,
+                "dispute_ruled",
+                (sale.auction.id.clone(), *resolution, case.appeal_deadline),
+            );
+
+            Ok(())
+        }
+        DisputeStatus::Appealed => {
+            let mut case = sale.case(env)?;
+            let first = case.rulings.get(0).ok_or(AuctionError::NoPendingRuling)?;
+            if first.resolver == *resolver {
+                return Err(AuctionError::Unauthorized);
+            }
+
+            case.rulings.push_back(ruling);
+            save_dispute_case(env, &sale.auction.id, &case);
+
+            settle_ruling(env, &mut sale, resolution)
+        }
+        _ => Err(AuctionError::NoOpenDispute),
     }
+}
 
-    let mut auction = get_auction(env, auction_id);
+// Appeal a ruling to a different resolver (buyer or seller, once)
+pub fn appeal_dispute(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    party: &Address,
+) -> Result<(), AuctionError> {
+    appeal(env, Sale::single(env, auction_id)?, party)
+}
 
-    if auction.dispute_status != DisputeStatus::Open {
-        panic!("No open dispute for this auction");
+pub(super) fn appeal(env: &Env, mut sale: Sale, party: &Address) -> Result<(), AuctionError> {
+    party.require_auth();
+
+    if sale.dispute_status() != DisputeStatus::Ruled {
+        return Err(AuctionError::NoPendingRuling);
     }
 
-    // Update auction
-    auction.dispute_status = *resolution;
-    auction.status = AuctionStatus::Completed;
-    save_auction(env, auction_id, &auction);
+    let case = sale.case(env)?;
+    if *party != case.buyer && *party != case.seller {
+        return Err(AuctionError::Unauthorized);
+    }
+    if env.ledger().timestamp() > case.appeal_deadline {
+        return Err(AuctionError::DisputeWindowClosed);
+    }
+
+    sale.set_dispute_status(DisputeStatus::Appealed);
+    sale.save(env);
+
+    // Emit event
+    sale.publish(
+        env,
+        "dispute_appealed",
+        (sale.auction.id.clone(), party.clone()),
+    );
+
+    Ok(())
+}
+
+// Settle a ruling nobody appealed once the appeal deadline has passed (anyone)
+pub fn finalize_dispute(env: &Env, auction_id: &BytesN<32>) -> Result<(), AuctionError> {
+    finalize_ruling(env, Sale::single(env, auction_id)?)
+}
+
+pub(super) fn finalize_ruling(env: &Env, mut sale: Sale) -> Result<(), AuctionError> {
+    if sale.dispute_status() != DisputeStatus::Ruled {
+        return Err(AuctionError::NoPendingRuling);
+    }
+
+    let case = sale.case(env)?;
+    if env.ledger().timestamp() <= case.appeal_deadline {
+        return Err(AuctionError::DisputeWindowOpen);
+    }
+
+    let ruling = case.rulings.get(0).ok_or(AuctionError::NoPendingRuling)?;
+    settle_ruling(env, &mut sale, &ruling.resolution)
+}
+
+// Refund the buyer when the seller let the response deadline pass (anyone)
+pub fn apply_default_judgment(env: &Env, auction_id: &BytesN<32>) -> Result<(), AuctionError> {
+    default_judgment(env, Sale::single(env, auction_id)?)
+}
+
+pub(super) fn default_judgment(env: &Env, mut sale: Sale) -> Result<(), AuctionError> {
+    if sale.dispute_status() != DisputeStatus::Open {
+        return Err(AuctionError::NoOpenDispute);
+    }
+
+    let case = sale.case(env)?;
+    if case.seller_responded {
+        return Err(AuctionError::DisputeNotAllowed);
+    }
+    if env.ledger().timestamp() <= case.response_deadline {
+        return Err(AuctionError::SellerResponsePending);
+    }
 
     // Emit event
-    env.events().publish(
-        (Symbol::new(env, "dispute_resolved"), auction_id.clone()),
-        (auction_id.clone(), *resolution),
+    sale.publish(
+        env,
+        "default_judgment",
+        (sale.auction.id.clone(), case.buyer),
     );
+
+    settle_ruling(env, &mut sale, &DisputeResolution::RefundBuyer)
+}
+
+// Final dispute status and buyer percentage for a resolution
+fn resolution_outcome(
+    resolution: &DisputeResolution,
+) -> Result<(DisputeStatus, u32), AuctionError> {
+    match *resolution {
+        DisputeResolution::RefundBuyer => Ok((DisputeStatus::ResolvedForBuyer, 100)),
+        DisputeResolution::PaySeller => Ok((DisputeStatus::ResolvedForSeller, 0)),
+        DisputeResolution::Split(percentage) => {
+            if percentage > 100 {
+                return Err(AuctionError::InvalidSplit);
+            }
+            Ok((DisputeStatus::ResolvedSplit, percentage))
+        }
+    }
+}
+
+// Move the escrowed funds according to a final resolution and close the dispute.
+// A single sale settles the whole escrow; an allocation only that winner's payment
+fn settle_ruling(
+    env: &Env,
+    sale: &mut Sale,
+    resolution: &DisputeResolution,
+) -> Result<(), AuctionError> {
+    let (status, buyer_percentage) = resolution_outcome(resolution)?;
+
+    match sale.allocation {
+        Some(index) => {
+            let mut allocation = sale.auction.allocations.get(index).unwrap();
+            settle_allocation(
+                env,
+                &mut sale.auction,
+                &allocation,
+                buyer_percentage,
+                SettlementKind::DisputePayout,
+            )?;
+            allocation.settled = true;
+            sale.auction.allocations.set(index, allocation);
+            sale.set_dispute_status(status);
+            complete_when_settled(env, &mut sale.auction);
+        }
+        None => {
+            settle_dispute(env, &mut sale.auction, &sale.buyer, buyer_percentage)?;
+            sale.set_dispute_status(status);
+            sale.auction.status = AuctionStatus::Completed;
+        }
+    }
+    sale.save(env);
+
+    // Emit event
+    sale.publish(env, "dispute_resolved", (sale.auction.id.clone(), status));
+
+    Ok(())
 }
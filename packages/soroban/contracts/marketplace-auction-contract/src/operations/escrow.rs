@@ -0,0 +1,181 @@
+use super::storage::add_fee_total;
+use crate::datatype::{Allocation, Auction, AuctionError, Settlement, SettlementKind};
+use soroban_sdk::{token, Address, Env, Symbol};
+
+// Pull funds from a bidder into contract custody
+pub fn collect(
+    env: &Env,
+    auction: &mut Auction,
+    from: &Address,
+    amount: i128,
+) -> Result<(), AuctionError> {
+    if amount <= 0 {
+        return Err(AuctionError::InvalidAmount);
+    }
+
+    let token = token::Client::new(env, &auction.payment_token);
+    token.transfer(from, &env.current_contract_address(), &amount);
+
+    auction.escrowed_amount += amount;
+
+    Ok(())
+}
+
+// Return escrowed funds to a bidder (e.g. when outbid)
+pub fn refund(
+    env: &Env,
+    auction: &mut Auction,
+    to: &Address,
+    amount: i128,
+) -> Result<(), AuctionError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    if amount > auction.escrowed_amount {
+        return Err(AuctionError::InsufficientEscrow);
+    }
+
+    let token = token::Client::new(env, &auction.payment_token);
+    token.transfer(&env.current_contract_address(), to, &amount);
+
+    auction.escrowed_amount -= amount;
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "bid_refunded"), auction.id.clone()),
+        (auction.id.clone(), to.clone(), amount),
+    );
+
+    Ok(())
+}
+
+// Pay escrowed funds out, recording the settlement on the auction
+pub fn settle(
+    env: &Env,
+    auction: &mut Auction,
+    recipient: &Address,
+    amount: i128,
+    kind: SettlementKind,
+) -> Result<(), AuctionError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    if amount > auction.escrowed_amount {
+        return Err(AuctionError::InsufficientEscrow);
+    }
+
+    let token = token::Client::new(env, &auction.payment_token);
+    token.transfer(&env.current_contract_address(), recipient, &amount);
+
+    auction.escrowed_amount -= amount;
+    auction.settlements.push_back(Settlement {
+        recipient: recipient.clone(),
+        amount,
+        kind,
+        timestamp: env.ledger().timestamp(),
+    });
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "escrow_settled"), auction.id.clone()),
+        (auction.id.clone(), recipient.clone(), amount, kind),
+    );
+
+    Ok(())
+}
+
+// Pay the platform fee on a commissionable amount out of escrow, returning the fee
+fn charge_fee(env: &Env, auction: &mut Auction, amount: i128) -> Result<i128, AuctionError> {
+    let fee = auction.fee.fee_on(amount);
+    if fee > 0 {
+        let recipient = auction.fee.recipient.clone();
+        settle(env, auction, &recipient, fee, SettlementKind::PlatformFee)?;
+        add_fee_total(env, &auction.payment_token, fee);
+    }
+
+    Ok(fee)
+}
+
+// Shipping cost the buyer paid into escrow, if any
+fn quoted_shipping(auction: &Auction) -> i128 {
+    if auction.has_shipping_quote {
+        auction.shipping_quote.cost
+    } else {
+        0
+    }
+}
+
+// Release everything held for this auction to the seller, less the platform
+// fee. The quoted shipping cost passes through without commission
+pub fn release_to_seller(env: &Env, auction: &mut Auction) -> Result<(), AuctionError> {
+    let shipping = quoted_shipping(auction);
+    charge_fee(env, auction, auction.escrowed_amount - shipping)?;
+
+    let seller = auction.product.seller.clone();
+    settle(env, auction, &seller, shipping, SettlementKind::Shipping)?;
+    settle(
+        env,
+        auction,
+        &seller,
+        auction.escrowed_amount,
+        SettlementKind::Sale,
+    )
+}
+
+// Split everything held for this auction between buyer and seller. The
+// seller's share carries the platform fee like a sale, except for the part
+// that covers the quoted shipping cost
+pub fn settle_dispute(
+    env: &Env,
+    auction: &mut Auction,
+    buyer: &Address,
+    buyer_percentage: u32,
+) -> Result<(), AuctionError> {
+    let buyer_amount = auction.escrowed_amount * buyer_percentage as i128 / 100;
+    let seller_amount = auction.escrowed_amount - buyer_amount;
+    let seller = auction.product.seller.clone();
+
+    settle(
+        env,
+        auction,
+        buyer,
+        buyer_amount,
+        SettlementKind::DisputeRefund,
+    )?;
+
+    let shipping = quoted_shipping(auction).min(seller_amount);
+    let fee = charge_fee(env, auction, seller_amount - shipping)?;
+    settle(
+        env,
+        auction,
+        &seller,
+        seller_amount - fee,
+        SettlementKind::DisputePayout,
+    )
+}
+
+// Settle one multi-unit allocation: the winner gets `buyer_percentage` of its
+// payment back and the seller the rest, less the platform fee
+pub fn settle_allocation(
+    env: &Env,
+    auction: &mut Auction,
+    allocation: &Allocation,
+    buyer_percentage: u32,
+    seller_kind: SettlementKind,
+) -> Result<(), AuctionError> {
+    let amount = allocation.amount();
+    let buyer_amount = amount * buyer_percentage as i128 / 100;
+    let seller_amount = amount - buyer_amount;
+    let seller = auction.product.seller.clone();
+
+    settle(
+        env,
+        auction,
+        &allocation.bidder,
+        buyer_amount,
+        SettlementKind::DisputeRefund,
+    )?;
+
+    let fee = charge_fee(env, auction, seller_amount)?;
+    settle(env, auction, &seller, seller_amount - fee, seller_kind)
+}
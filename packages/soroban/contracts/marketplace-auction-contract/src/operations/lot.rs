@@ -0,0 +1,229 @@
+use super::auction::{list_auction, next_auction_id, product_id, validate_listing};
+use super::shipping::{check_reporter, mark_delivered, validate_transition};
+use super::storage::{
+    get_auction, get_fee_config, get_lot, get_payment_token, is_verifier, save_auction, save_lot,
+};
+use super::{attest, refresh_authentication};
+use crate::datatype::{
+    Auction, AuctionError, AuctionOptions, AuctionStatus, LotItem, LotItemInput, Product,
+    ProductCondition, ShippingStatus,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+// Upper bound on products sold together in one lot
+const MAX_LOT_ITEMS: u32 = 20;
+
+// Create an auction selling several products together as a single lot
+pub fn create_lot_auction(
+    env: &Env,
+    seller: &Address,
+    name: &String,
+    description: &String,
+    items: &Vec<LotItemInput>,
+    reserve_price: &i128,
+    start_time: &u64,
+    end_time: &u64,
+    options: &AuctionOptions,
+) -> Result<BytesN<32>, AuctionError> {
+    seller.require_auth();
+
+    if items.is_empty() || items.len() > MAX_LOT_ITEMS {
+        return Err(AuctionError::InvalidInventory);
+    }
+    validate_listing(1, *reserve_price, *start_time, *end_time, options)?;
+
+    let (counter, auction_id) = next_auction_id(env);
+
+    // Each item gets its own product, ID derived from the counter and its position
+    let mut lot = Vec::new(env);
+    let mut images = Vec::new(env);
+    let mut condition = ProductCondition::New;
+    for (index, item) in items.iter().enumerate() {
+        let mut item_bytes = Bytes::from_slice(env, &counter.to_be_bytes());
+        item_bytes.append(&Bytes::from_slice(env, &(index as u32).to_be_bytes()));
+
+        // The lot is described by its worst item condition
+        if item.condition as u32 > condition as u32 {
+            condition = item.condition;
+        }
+        images.append(&item.images);
+
+        lot.push_back(LotItem {
+            product: Product {
+                id: env.crypto().sha256(&item_bytes).into(),
+                name: item.name,
+                description: item.description,
+                condition: item.condition,
+                images: item.images,
+                seller: seller.clone(),
+                inventory_count: 1,
+                is_authenticated: false,
+                attestations: Vec::new(env),
+            },
+            shipping_status: ShippingStatus::NotShipped,
+            delivered_at: 0,
+        });
+    }
+
+    // The lot as a whole is sold as one unit
+    let product = Product {
+        id: product_id(env, counter),
+        name: name.clone(),
+        description: description.clone(),
+        condition,
+        images,
+        seller: seller.clone(),
+        inventory_count: 1,
+        is_authenticated: false,
+        attestations: Vec::new(env),
+    };
+
+    let mut auction = Auction::new(
+        env,
+        auction_id.clone(),
+        product,
+        *start_time,
+        *end_time,
+        *reserve_price,
+        get_payment_token(env)?,
+        get_fee_config(env),
+        options.clone(),
+    );
+    auction.lot_size = lot.len();
+    save_lot(env, &auction_id, &lot);
+
+    Ok(list_auction(env, counter, &auction))
+}
+
+// Record a verifier's attestation on one item of a lot. The lot counts as
+// authenticated once every item is
+pub fn verify_lot_item(
+    env: &Env,
+    verifier: &Address,
+    auction_id: &BytesN<32>,
+    index: &u32,
+    is_authentic: &bool,
+    notes_hash: &BytesN<32>,
+) -> Result<(), AuctionError> {
+    verifier.require_auth();
+
+    // Check if verifier is authorized
+    if !is_verifier(env, verifier) {
+        return Err(AuctionError::Unauthorized);
+    }
+
+    let mut auction = get_auction(env, auction_id)?;
+    let mut lot = lot_of(env, &auction)?;
+    let mut item = lot.get(*index).ok_or(AuctionError::InvalidInventory)?;
+
+    attest(env, &mut item.product, verifier, *is_authentic, notes_hash);
+    lot.set(*index, item.clone());
+    save_lot(env, auction_id, &lot);
+
+    refresh_authentication(env, &mut auction);
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "lot_item_verified"), auction_id.clone()),
+        (
+            auction_id.clone(),
+            *index,
+            verifier.clone(),
+            *is_authentic,
+            item.product.is_authenticated,
+        ),
+    );
+
+    Ok(())
+}
+
+// Update the shipping status of one item of a lot. The seller or the quoted
+// carrier can report progress, but only the carrier can report delivery
+pub fn update_lot_item_status(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    caller: &Address,
+    index: &u32,
+    new_status: &ShippingStatus,
+) -> Result<(), AuctionError> {
+    caller.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+    check_reporter(env, &auction, caller, *new_status)?;
+
+    set_item_status(env, &mut auction, *index, *new_status)
+}
+
+// Confirm delivery of one item of a lot as the winning bidder
+pub fn confirm_lot_item_delivery(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    index: &u32,
+) -> Result<(), AuctionError> {
+    buyer.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+    if !auction.has_highest_bid || auction.highest_bidder != *buyer {
+        return Err(AuctionError::NotHighestBidder);
+    }
+
+    set_item_status(env, &mut auction, *index, ShippingStatus::Delivered)
+}
+
+// Get the products of a lot auction
+pub(super) fn lot_of(env: &Env, auction: &Auction) -> Result<Vec<LotItem>, AuctionError> {
+    if auction.lot_size == 0 {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    Ok(get_lot(env, &auction.id))
+}
+
+// Move one lot item forward, then bring the lot's shipping status up to its
+// least advanced item. The lot is delivered once every item is
+fn set_item_status(
+    env: &Env,
+    auction: &mut Auction,
+    index: u32,
+    new_status: ShippingStatus,
+) -> Result<(), AuctionError> {
+    let mut lot = lot_of(env, auction)?;
+    // A disputed or completed sale no longer takes shipping updates
+    if auction.status != AuctionStatus::Ended {
+        return Err(AuctionError::AuctionNotSold);
+    }
+    if !auction.has_shipping {
+        return Err(AuctionError::NoShippingInfo);
+    }
+
+    let mut item = lot.get(index).ok_or(AuctionError::InvalidInventory)?;
+    validate_transition(item.shipping_status, new_status)?;
+    item.shipping_status = new_status;
+    if new_status == ShippingStatus::Delivered {
+        item.delivered_at = env.ledger().timestamp();
+    }
+    lot.set(index, item);
+    save_lot(env, &auction.id, &lot);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "lot_item_shipping"), auction.id.clone()),
+        (auction.id.clone(), index, new_status),
+    );
+
+    let mut least = ShippingStatus::Delivered;
+    for item in lot.iter() {
+        if (item.shipping_status as u32) < (least as u32) {
+            least = item.shipping_status;
+        }
+    }
+    if least == ShippingStatus::Delivered {
+        mark_delivered(env, auction);
+    } else {
+        auction.shipping_status = least;
+    }
+    save_auction(env, &auction.id.clone(), auction);
+
+    Ok(())
+}
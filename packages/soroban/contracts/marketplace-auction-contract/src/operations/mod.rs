@@ -1,36 +1,76 @@
 mod admin;
+mod allocation;
 mod auction;
 mod bid;
+mod clearing;
 mod dispute;
+mod escrow;
+mod lot;
+mod proxy;
 mod query;
+mod reverse;
+mod sealed;
 mod shipping;
 mod storage;
 
 // Re-export all functions to avoid exposing module structure details
-pub use admin::{add_resolver, add_verifier, initialize};
-pub use auction::create_auction;
-pub use bid::place_bid;
-pub use dispute::{open_dispute, resolve_dispute};
+pub use admin::{
+    accept_admin, add_carrier, add_resolver, add_verifier, initialize, propose_admin,
+    remove_carrier, remove_resolver, remove_verifier, remove_zone_rate, set_attestation_quorum,
+    set_dispute_deadlines, set_dispute_window, set_fee_config, set_shipping_rates, set_zone_rate,
+};
+pub use allocation::{
+    allocation_default_judgment, appeal_allocation_dispute, confirm_allocation_delivery,
+    finalize_allocation, finalize_allocation_dispute, open_allocation_dispute,
+    resolve_allocation_dispute, submit_allocation_evidence,
+};
+pub use auction::{create_auction, relist_auction};
+pub use bid::{accept_price, buy_now, place_bid};
+pub use dispute::{
+    appeal_dispute, apply_default_judgment, finalize_dispute, open_dispute, resolve_dispute,
+    submit_evidence,
+};
+pub use lot::{
+    confirm_lot_item_delivery, create_lot_auction, update_lot_item_status, verify_lot_item,
+};
+pub use proxy::place_proxy_bid;
 pub use query::{
-    calculate_shipping_cost, query_auction, query_auctions, query_user_bidding_auctions,
-    query_user_selling_auctions,
+    calculate_shipping_cost, query_active_auctions, query_admin, query_allocation_dispute,
+    query_allocations, query_attestation_quorum, query_auction, query_auctions,
+    query_auctions_by_status, query_auctions_ending_before, query_bids, query_carriers,
+    query_current_price, query_dispute, query_fee_config, query_lot, query_product_lineage,
+    query_resolvers, query_seller_carriers, query_shipping_rates, query_shipping_zone,
+    query_total_fees, query_user_bid, query_user_bidding_auctions, query_user_outbid_auctions,
+    query_user_selling_auctions, query_user_winning_auctions, query_user_won_auctions,
+    query_verifiers, query_visible_price,
+};
+pub use reverse::{accept_offer, create_service_request, place_offer};
+pub use sealed::{commit_bid, reveal_bid};
+pub use shipping::{
+    add_shipping_info, confirm_delivery, finalize_delivery, quote_shipping,
+    set_carriers_for_seller, update_shipping_status,
 };
-pub use shipping::{add_shipping_info, update_shipping_status};
 
 // Export additional functions defined at module level
-pub fn start_auction(env: &soroban_sdk::Env, auction_id: &soroban_sdk::BytesN<32>) {
-    let mut auction = storage::get_auction(env, auction_id);
+pub fn start_auction(
+    env: &soroban_sdk::Env,
+    auction_id: &soroban_sdk::BytesN<32>,
+) -> Result<(), crate::datatype::AuctionError> {
+    let mut auction = storage::get_auction(env, auction_id)?;
 
     // Only the seller can start the auction
     auction.product.seller.require_auth();
 
     if auction.status != crate::datatype::AuctionStatus::Pending {
-        panic!("Auction is not in pending state");
+        return Err(crate::datatype::AuctionError::AuctionNotPending);
     }
 
     let current_time = env.ledger().timestamp();
     if current_time < auction.start_time {
-        panic!("Auction start time has not been reached");
+        return Err(crate::datatype::AuctionError::AuctionNotStarted);
+    }
+    if current_time >= auction.end_time {
+        return Err(crate::datatype::AuctionError::AuctionAlreadyEnded);
     }
 
     auction.status = crate::datatype::AuctionStatus::Active;
@@ -44,42 +84,123 @@ pub fn start_auction(env: &soroban_sdk::Env, auction_id: &soroban_sdk::BytesN<32
         ),
         auction_id.clone(),
     );
+
+    Ok(())
 }
 
-pub fn end_auction(env: &soroban_sdk::Env, auction_id: &soroban_sdk::BytesN<32>) {
-    let mut auction = storage::get_auction(env, auction_id);
+pub fn end_auction(
+    env: &soroban_sdk::Env,
+    auction_id: &soroban_sdk::BytesN<32>,
+) -> Result<(), crate::datatype::AuctionError> {
+    let mut auction = storage::get_auction(env, auction_id)?;
+
+    // A Pending auction that was never started expires once its end time passes
+    if auction.status == crate::datatype::AuctionStatus::Pending
+        && env.ledger().timestamp() >= auction.end_time
+    {
+        expire_auction(env, &mut auction);
+        return Ok(());
+    }
+
+    // Sealed-bid auctions go through a reveal phase before they end
+    if let crate::datatype::AuctionType::Sealed(_) = auction.options.auction_type {
+        return sealed::end_sealed_auction(env, auction_id);
+    }
 
     if auction.status != crate::datatype::AuctionStatus::Active {
-        panic!("Auction is not active");
+        return Err(crate::datatype::AuctionError::AuctionNotActive);
     }
 
     let current_time = env.ledger().timestamp();
     if current_time < auction.end_time {
-        panic!("Auction end time has not been reached");
+        return Err(crate::datatype::AuctionError::AuctionNotEnded);
+    }
+
+    let reserve_met = auction.reserve_met();
+    match auction.options.auction_type {
+        // Multi-unit auctions are cleared at a uniform price
+        crate::datatype::AuctionType::MultiUnit => clearing::clear_multi_unit(env, &mut auction)?,
+        // A winning proxy only pays the visible price
+        crate::datatype::AuctionType::English if reserve_met => {
+            proxy::settle_winning_proxy(env, &mut auction)?
+        }
+        // A highest bid below reserve does not sell; its escrow goes back
+        crate::datatype::AuctionType::English => proxy::release_leader(env, &mut auction)?,
+        _ => {}
     }
 
+    if reserve_met {
+        close_auction(env, &mut auction);
+    } else {
+        close_unsold_auction(env, &mut auction);
+    }
+
+    Ok(())
+}
+
+// Move an auction to Ended, persist it and announce it
+// Shared by end_auction and the paths that end an auction early
+pub(crate) fn close_auction(env: &soroban_sdk::Env, auction: &mut crate::datatype::Auction) {
     auction.status = crate::datatype::AuctionStatus::Ended;
-    storage::save_auction(env, auction_id, &auction);
+    storage::save_auction(env, &auction.id, auction);
 
     // Emit event
     env.events().publish(
         (
             soroban_sdk::Symbol::new(env, "auction_ended"),
-            auction_id.clone(),
+            auction.id.clone(),
+        ),
+        auction.id.clone(),
+    );
+}
+
+// Move an auction that ended without a bid at or above reserve to ReserveNotMet.
+// The item is not sold, so it cannot be shipped or disputed
+pub(crate) fn close_unsold_auction(env: &soroban_sdk::Env, auction: &mut crate::datatype::Auction) {
+    auction.status = crate::datatype::AuctionStatus::ReserveNotMet;
+    storage::save_auction(env, &auction.id, auction);
+
+    // Emit event
+    env.events().publish(
+        (
+            soroban_sdk::Symbol::new(env, "reserve_not_met"),
+            auction.id.clone(),
+        ),
+        (
+            auction.id.clone(),
+            auction.highest_bid_amount,
+            auction.reserve_price,
         ),
-        auction_id.clone(),
     );
 }
 
-pub fn cancel_auction(env: &soroban_sdk::Env, auction_id: &soroban_sdk::BytesN<32>) {
-    let mut auction = storage::get_auction(env, auction_id);
+// Move a Pending auction whose end time has passed to Expired, persist it and announce it
+pub(crate) fn expire_auction(env: &soroban_sdk::Env, auction: &mut crate::datatype::Auction) {
+    auction.status = crate::datatype::AuctionStatus::Expired;
+    storage::save_auction(env, &auction.id, auction);
+
+    // Emit event
+    env.events().publish(
+        (
+            soroban_sdk::Symbol::new(env, "auction_expired"),
+            auction.id.clone(),
+        ),
+        auction.id.clone(),
+    );
+}
+
+pub fn cancel_auction(
+    env: &soroban_sdk::Env,
+    auction_id: &soroban_sdk::BytesN<32>,
+) -> Result<(), crate::datatype::AuctionError> {
+    let mut auction = storage::get_auction(env, auction_id)?;
 
     // Only the seller can cancel an auction
     auction.product.seller.require_auth();
 
     // Can only cancel pending auctions
     if auction.status != crate::datatype::AuctionStatus::Pending {
-        panic!("Only pending auctions can be cancelled");
+        return Err(crate::datatype::AuctionError::AuctionNotPending);
     }
 
     auction.status = crate::datatype::AuctionStatus::Cancelled;
@@ -93,25 +214,92 @@ pub fn cancel_auction(env: &soroban_sdk::Env, auction_id: &soroban_sdk::BytesN<3
         ),
         auction_id.clone(),
     );
+
+    Ok(())
+}
+
+// Append an attestation to a product and recount its authentication status.
+// Every verdict stays on the product as an audit trail
+pub(crate) fn attest(
+    env: &soroban_sdk::Env,
+    product: &mut crate::datatype::Product,
+    verifier: &soroban_sdk::Address,
+    is_authentic: bool,
+    notes_hash: &soroban_sdk::BytesN<32>,
+) {
+    product
+        .attestations
+        .push_back(crate::datatype::Attestation {
+            verifier: verifier.clone(),
+            is_authentic,
+            timestamp: env.ledger().timestamp(),
+            notes_hash: notes_hash.clone(),
+        });
+
+    // Update authentication status
+    product.is_authenticated = is_authenticated(env, product);
 }
 
+// Whether the latest verdicts of current verifiers are positive often enough
+// to meet the current quorum. Removing a verifier or raising the quorum takes
+// effect at once
+pub(crate) fn is_authenticated(env: &soroban_sdk::Env, product: &crate::datatype::Product) -> bool {
+    let mut verdicts = soroban_sdk::Map::new(env);
+    for attestation in product.attestations.iter() {
+        verdicts.set(attestation.verifier, attestation.is_authentic);
+    }
+    let positive = verdicts
+        .iter()
+        .filter(|(verifier, verdict)| *verdict && storage::is_verifier(env, verifier))
+        .count() as u32;
+    positive >= storage::get_attestation_quorum(env)
+}
+
+// Recompute an auction's authentication status; a lot is authenticated once
+// every one of its products is
+pub(crate) fn refresh_authentication(
+    env: &soroban_sdk::Env,
+    auction: &mut crate::datatype::Auction,
+) {
+    auction.product.is_authenticated = if auction.lot_size > 0 {
+        storage::get_lot(env, &auction.id)
+            .iter()
+            .all(|item| is_authenticated(env, &item.product))
+    } else {
+        is_authenticated(env, &auction.product)
+    };
+}
+
+// Record a verifier's attestation on the product. The product counts as
+// authenticated while the latest verdicts of at least `quorum` verifiers are positive
 pub fn verify_product(
     env: &soroban_sdk::Env,
     verifier: &soroban_sdk::Address,
     auction_id: &soroban_sdk::BytesN<32>,
     is_authentic: &bool,
-) {
+    notes_hash: &soroban_sdk::BytesN<32>,
+) -> Result<(), crate::datatype::AuctionError> {
     verifier.require_auth();
 
     // Check if verifier is authorized
     if !storage::is_verifier(env, verifier) {
-        panic!("Not authorized to verify products");
+        return Err(crate::datatype::AuctionError::Unauthorized);
     }
 
-    let mut auction = storage::get_auction(env, auction_id);
+    let mut auction = storage::get_auction(env, auction_id)?;
 
-    // Update authentication status
-    auction.product.is_authenticated = *is_authentic;
+    // Lot items are attested one by one
+    if auction.lot_size > 0 {
+        return Err(crate::datatype::AuctionError::UnsupportedAuctionType);
+    }
+
+    attest(
+        env,
+        &mut auction.product,
+        verifier,
+        *is_authentic,
+        notes_hash,
+    );
     storage::save_auction(env, auction_id, &auction);
 
     // Emit event
@@ -120,6 +308,13 @@ pub fn verify_product(
             soroban_sdk::Symbol::new(env, "product_verified"),
             auction_id.clone(),
         ),
-        (auction_id.clone(), *is_authentic),
+        (
+            auction_id.clone(),
+            verifier.clone(),
+            *is_authentic,
+            auction.product.is_authenticated,
+        ),
     );
+
+    Ok(())
 }
@@ -0,0 +1,235 @@
+use super::bid::{apply_soft_close, bid_step, min_next_bid, validate_bidding_open};
+use super::escrow::{collect, refund};
+use super::storage::{
+    append_bid, get_auction, get_proxy_bid, remove_proxy_bid, save_auction, set_proxy_bid,
+};
+use crate::datatype::{Auction, AuctionError, AuctionType, Bid, ProxyBid};
+use soroban_sdk::{Address, BytesN, Env, Symbol};
+
+// Register a hidden maximum bid; the contract bids on the bidder's behalf
+// in steps of the auction's bid increment, up to that maximum
+pub fn place_proxy_bid(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    bidder: &Address,
+    max_amount: &i128,
+    quantity: &u32,
+) -> Result<(), AuctionError> {
+    bidder.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+
+    if auction.options.auction_type != AuctionType::English {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    if auction.options.bid_increment <= 0 && auction.options.bid_increment_bps == 0 {
+        return Err(AuctionError::InvalidBidIncrement);
+    }
+    let current_time = validate_bidding_open(env, &auction)?;
+
+    if *quantity > auction.product.inventory_count {
+        return Err(AuctionError::InvalidQuantity);
+    }
+
+    let proxy = ProxyBid {
+        bidder: bidder.clone(),
+        max_amount: *max_amount,
+        quantity: *quantity,
+        timestamp: current_time,
+    };
+
+    let previous_bid = auction.highest_bid();
+    match &previous_bid {
+        // The current leader raises their own maximum; the visible price stays
+        // put unless it now reaches the reserve
+        Some(highest_bid) if highest_bid.bidder == *bidder => {
+            let escrowed = leader_escrow(env, &auction);
+            if *max_amount <= escrowed {
+                return Err(AuctionError::ProxyMaxTooLow);
+            }
+            collect(env, &mut auction, bidder, *max_amount - escrowed)?;
+            set_proxy_bid(env, auction_id, &proxy);
+            lift_to_reserve(env, &mut auction, &proxy);
+        }
+        _ => {
+            let minimum = min_next_bid(&auction);
+            if *max_amount < minimum {
+                return Err(AuctionError::BidTooLow);
+            }
+
+            match leading_proxy(env, &auction) {
+                // The leading proxy holds (earlier proxy wins ties); the
+                // challenger is recorded at their maximum
+                Some(leader) if leader.max_amount >= *max_amount => {
+                    append_bid(env, &mut auction, &proxy_as_bid(&proxy, *max_amount));
+                    raise_proxy_leader(env, &mut auction, &leader, *max_amount);
+                }
+                // The challenger takes the lead, one increment above the old ceiling
+                _ => {
+                    let visible = match &previous_bid {
+                        Some(_) => {
+                            let ceiling = leader_escrow(env, &auction);
+                            proxy_visible(
+                                &auction,
+                                ceiling + bid_step(&auction, ceiling),
+                                *max_amount,
+                            )
+                        }
+                        None => proxy_visible(&auction, minimum, *max_amount),
+                    };
+
+                    collect(env, &mut auction, bidder, *max_amount)?;
+                    release_leader(env, &mut auction)?;
+                    set_proxy_bid(env, auction_id, &proxy);
+
+                    let bid = proxy_as_bid(&proxy, visible);
+                    auction.set_highest_bid(Some(bid.clone()));
+                    append_bid(env, &mut auction, &bid);
+                }
+            }
+        }
+    }
+
+    apply_soft_close(env, &mut auction, current_time);
+    save_auction(env, auction_id, &auction);
+
+    // Emit event (the maximum stays hidden)
+    env.events().publish(
+        (Symbol::new(env, "proxy_bid_placed"), auction_id.clone()),
+        (
+            auction_id.clone(),
+            bidder.clone(),
+            auction.highest_bid_amount,
+        ),
+    );
+
+    Ok(())
+}
+
+// The proxy bid of the current highest bidder, if they have one
+pub fn leading_proxy(env: &Env, auction: &Auction) -> Option<ProxyBid> {
+    if !auction.has_highest_bid {
+        return None;
+    }
+    get_proxy_bid(env, &auction.id, &auction.highest_bidder)
+}
+
+// How much the current highest bidder has in escrow: their proxy maximum,
+// or the visible bid for a manual bid
+fn leader_escrow(env: &Env, auction: &Auction) -> i128 {
+    match leading_proxy(env, auction) {
+        Some(proxy) => proxy.max_amount,
+        None => auction.highest_bid_amount,
+    }
+}
+
+// Refund the current highest bidder in full and drop their proxy, if any
+pub fn release_leader(env: &Env, auction: &mut Auction) -> Result<(), AuctionError> {
+    if !auction.has_highest_bid {
+        return Ok(());
+    }
+
+    let leader = auction.highest_bidder.clone();
+    let escrowed = leader_escrow(env, auction);
+    remove_proxy_bid(env, &auction.id, &leader);
+    refund(env, auction, &leader, escrowed)
+}
+
+// Raise the leading proxy's hidden maximum, escrowing the difference. The
+// visible price stays put unless the new maximum lifts it to the reserve
+pub fn raise_proxy_max(
+    env: &Env,
+    auction: &mut Auction,
+    proxy: &ProxyBid,
+    max_amount: i128,
+) -> Result<(), AuctionError> {
+    if max_amount <= proxy.max_amount {
+        return Err(AuctionError::ProxyMaxTooLow);
+    }
+
+    collect(env, auction, &proxy.bidder, max_amount - proxy.max_amount)?;
+    let raised = ProxyBid {
+        max_amount,
+        ..proxy.clone()
+    };
+    set_proxy_bid(env, &auction.id, &raised);
+    lift_to_reserve(env, auction, &raised);
+
+    Ok(())
+}
+
+// Visible price of a proxy bid: `amount` capped at its maximum, and never
+// below the reserve price once the maximum meets it
+fn proxy_visible(auction: &Auction, amount: i128, max_amount: i128) -> i128 {
+    if max_amount >= auction.reserve_price {
+        amount.max(auction.reserve_price).min(max_amount)
+    } else {
+        amount.min(max_amount)
+    }
+}
+
+// Raise the leading proxy's visible price to the reserve once a higher
+// maximum lets it meet the reserve
+fn lift_to_reserve(env: &Env, auction: &mut Auction, proxy: &ProxyBid) {
+    let visible = proxy_visible(auction, auction.highest_bid_amount, proxy.max_amount);
+    if visible <= auction.highest_bid_amount {
+        return;
+    }
+
+    let bid = Bid {
+        bidder: proxy.bidder.clone(),
+        amount: visible,
+        timestamp: env.ledger().timestamp(),
+        quantity: proxy.quantity,
+    };
+    auction.set_highest_bid(Some(bid.clone()));
+    append_bid(env, auction, &bid);
+}
+
+// Answer a competing bid on behalf of the leading proxy
+pub fn raise_proxy_leader(env: &Env, auction: &mut Auction, leader: &ProxyBid, competing: i128) {
+    let visible = proxy_visible(
+        auction,
+        competing + bid_step(auction, competing),
+        leader.max_amount,
+    );
+    let bid = Bid {
+        bidder: leader.bidder.clone(),
+        amount: visible,
+        timestamp: env.ledger().timestamp(),
+        quantity: leader.quantity,
+    };
+
+    auction.set_highest_bid(Some(bid.clone()));
+    append_bid(env, auction, &bid);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "bid_placed"), auction.id.clone()),
+        (auction.id.clone(), bid.bidder, bid.amount, bid.quantity),
+    );
+}
+
+// Refund the unused part of the winning proxy's maximum
+pub fn settle_winning_proxy(env: &Env, auction: &mut Auction) -> Result<(), AuctionError> {
+    if let Some(proxy) = leading_proxy(env, auction) {
+        remove_proxy_bid(env, &auction.id, &proxy.bidder);
+        refund(
+            env,
+            auction,
+            &proxy.bidder,
+            proxy.max_amount - auction.highest_bid_amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn proxy_as_bid(proxy: &ProxyBid, amount: i128) -> Bid {
+    Bid {
+        bidder: proxy.bidder.clone(),
+        amount,
+        timestamp: proxy.timestamp,
+        quantity: proxy.quantity,
+    }
+}
@@ -1,82 +1,384 @@
-use super::storage::get_auction;
-use crate::datatype::{Auction, StorageKey};
-use soroban_sdk::{Address, BytesN, Env, Map, String, Vec};
+use super::lot::lot_of;
+use super::shipping::shipping_cost_for;
+use super::storage::{
+    get_admin, get_all_bids, get_attestation_quorum, get_auction, get_auction_count,
+    get_auction_index, get_bid_page, get_dispute_case, get_fee_config, get_fee_total,
+    get_product_lineage, get_role_members, get_seller_carriers, get_shipping_rates,
+    get_shipping_zone, get_user_auctions, get_user_bid, try_get_auction,
+};
+use super::{is_authenticated, refresh_authentication};
+use crate::datatype::{
+    Allocation, Auction, AuctionError, AuctionPage, AuctionStatus, AuctionType, Bid, DisputeCase,
+    FeeConfig, LotItem, ShippingRates, StorageKey, UserBid,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+// Largest page a discovery query will return
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Most auctions a single discovery query will inspect before returning
+const MAX_SCAN: u32 = 200;
 
 // Query a single auction
 pub fn query_auction(env: &Env, auction_id: &BytesN<32>) -> Option<Auction> {
-    let auctions: Map<BytesN<32>, Auction> = env
-        .storage()
-        .instance()
-        .get(&StorageKey::Auctions)
-        .unwrap_or_else(|| Map::<BytesN<32>, Auction>::new(env));
+    read_auction(env, auction_id)
+}
 
-    auctions.get(auction_id.clone())
+// Load an auction as it currently stands: authentication is recomputed and a
+// Pending auction past its end time reads as Expired, even before anyone
+// calls end_auction
+fn read_auction(env: &Env, auction_id: &BytesN<32>) -> Option<Auction> {
+    let mut auction = try_get_auction(env, auction_id)?;
+    refresh_authentication(env, &mut auction);
+    if auction.status == AuctionStatus::Pending && env.ledger().timestamp() >= auction.end_time {
+        auction.status = AuctionStatus::Expired;
+    }
+    Some(auction)
 }
 
 // Query auctions by seller
 pub fn query_user_selling_auctions(env: &Env, user: &Address) -> Vec<BytesN<32>> {
-    env.storage()
-        .instance()
-        .get(&StorageKey::UserSelling(user.clone()))
-        .unwrap_or_else(|| Vec::new(env))
+    get_user_auctions(env, &StorageKey::UserSelling(user.clone()))
+}
+
+// Query the products of a lot auction with their verification and shipping progress
+pub fn query_lot(env: &Env, auction_id: &BytesN<32>) -> Result<Vec<LotItem>, AuctionError> {
+    let auction = get_auction(env, auction_id)?;
+    let mut lot = lot_of(env, &auction)?;
+    for (index, mut item) in lot.clone().iter().enumerate() {
+        item.product.is_authenticated = is_authenticated(env, &item.product);
+        lot.set(index as u32, item);
+    }
+    Ok(lot)
+}
+
+// Query every auction that listed a product, oldest first
+pub fn query_product_lineage(env: &Env, product_id: &BytesN<32>) -> Vec<BytesN<32>> {
+    get_product_lineage(env, product_id)
 }
 
 // Query auctions by bidder
 pub fn query_user_bidding_auctions(env: &Env, user: &Address) -> Vec<BytesN<32>> {
-    env.storage()
-        .instance()
-        .get(&StorageKey::UserBidding(user.clone()))
-        .unwrap_or_else(|| Vec::new(env))
+    get_user_auctions(env, &StorageKey::UserBidding(user.clone()))
+}
+
+// A bidder's latest bid on an auction, with whether it is currently winning
+pub fn query_user_bid(env: &Env, auction_id: &BytesN<32>, bidder: &Address) -> Option<UserBid> {
+    let mut record = get_user_bid(env, bidder, auction_id)?;
+    if let Some(auction) = try_get_auction(env, auction_id) {
+        record.is_winning = is_winning(env, &auction, bidder);
+    }
+    Some(record)
+}
+
+// Auctions still running on which the user holds a winning bid
+pub fn query_user_winning_auctions(
+    env: &Env,
+    user: &Address,
+    cursor: &u32,
+    limit: &u32,
+) -> AuctionPage {
+    scan_user_bidding(env, user, *cursor, *limit, |auction| {
+        (auction.status == AuctionStatus::Active || auction.status == AuctionStatus::Revealing)
+            && bid_standing(env, auction, user) == Some(true)
+    })
+}
+
+// Auctions on which another bidder has overtaken the user
+pub fn query_user_outbid_auctions(
+    env: &Env,
+    user: &Address,
+    cursor: &u32,
+    limit: &u32,
+) -> AuctionPage {
+    scan_user_bidding(env, user, *cursor, *limit, |auction| {
+        bid_standing(env, auction, user) == Some(false)
+    })
+}
+
+// Auctions the user has won (ended with the reserve met, including completed and disputed sales)
+pub fn query_user_won_auctions(
+    env: &Env,
+    user: &Address,
+    cursor: &u32,
+    limit: &u32,
+) -> AuctionPage {
+    scan_user_bidding(env, user, *cursor, *limit, |auction| {
+        matches!(
+            auction.status,
+            AuctionStatus::Ended | AuctionStatus::Completed | AuctionStatus::Disputed
+        ) && bid_standing(env, auction, user) == Some(true)
+    })
+}
+
+// Whether a bidder holds a winning position on an auction that can still sell
+fn is_winning(env: &Env, auction: &Auction, bidder: &Address) -> bool {
+    auction.status != AuctionStatus::ReserveNotMet
+        && bid_standing(env, auction, bidder) == Some(true)
+}
+
+// Whether a bidder is ahead (Some(true)) or has been outbid (Some(false));
+// None while nobody leads, e.g. before any sealed bid is revealed
+fn bid_standing(env: &Env, auction: &Auction, bidder: &Address) -> Option<bool> {
+    if auction.status == AuctionStatus::Cancelled || !auction.has_highest_bid {
+        return None;
+    }
+    // The accepted provider of a service request becomes its seller
+    if auction.options.auction_type == AuctionType::Reverse {
+        return Some(auction.product.seller == *bidder);
+    }
+    if auction.options.auction_type != AuctionType::MultiUnit {
+        return Some(auction.highest_bidder == *bidder);
+    }
+
+    // Multi-unit: once cleared, ahead means allocated units; while active,
+    // fewer units are bid above the bidder's best unit price than are for sale
+    if auction.status != AuctionStatus::Active {
+        return Some(auction.allocations.iter().any(|a| a.bidder == *bidder));
+    }
+    let bids = get_all_bids(env, auction);
+    let best = bids
+        .iter()
+        .filter(|bid| bid.bidder == *bidder)
+        .map(|bid| bid.amount)
+        .max()?;
+    let units_above: u32 = bids
+        .iter()
+        .filter(|bid| bid.amount > best)
+        .map(|bid| bid.quantity)
+        .sum();
+    Some(units_above < auction.product.inventory_count)
 }
 
 // Query multiple auctions at once
 pub fn query_auctions(env: &Env, auction_ids: &Vec<BytesN<32>>) -> Vec<Auction> {
-    let auctions: Map<BytesN<32>, Auction> = env
-        .storage()
-        .instance()
-        .get(&StorageKey::Auctions)
-        .unwrap_or_else(|| Map::<BytesN<32>, Auction>::new(env));
-
     let mut result = Vec::new(env);
     for id in auction_ids.iter() {
-        if let Some(auction) = auctions.get(id) {
+        if let Some(auction) = read_auction(env, &id) {
             result.push_back(auction);
         }
     }
     result
 }
 
-// Calculate shipping cost
-pub fn calculate_shipping_cost(
+// Query one page of an auction's bid history
+pub fn query_bids(env: &Env, auction_id: &BytesN<32>, page: &u32) -> Vec<Bid> {
+    get_bid_page(env, auction_id, *page)
+}
+
+// Query active auctions
+pub fn query_active_auctions(env: &Env, cursor: &u32, limit: &u32) -> AuctionPage {
+    scan_auctions(env, *cursor, *limit, |auction| {
+        auction.status == AuctionStatus::Active
+    })
+}
+
+// Query auctions still running (pending or active) whose end time is before a timestamp
+pub fn query_auctions_ending_before(
+    env: &Env,
+    timestamp: &u64,
+    cursor: &u32,
+    limit: &u32,
+) -> AuctionPage {
+    scan_auctions(env, *cursor, *limit, |auction| {
+        (auction.status == AuctionStatus::Pending || auction.status == AuctionStatus::Active)
+            && auction.end_time < *timestamp
+    })
+}
+
+// Query auctions by status
+pub fn query_auctions_by_status(
+    env: &Env,
+    status: &AuctionStatus,
+    cursor: &u32,
+    limit: &u32,
+) -> AuctionPage {
+    scan_auctions(env, *cursor, *limit, |auction| auction.status == *status)
+}
+
+// Walk auctions in creation order starting at `cursor`, collecting up to `limit`
+// matches. The walk stops early after MAX_SCAN auctions so a query never
+// exceeds resource limits; callers continue from `next_cursor`.
+fn scan_auctions<F>(env: &Env, cursor: u32, limit: u32, matches: F) -> AuctionPage
+where
+    F: Fn(&Auction) -> bool,
+{
+    let count = get_auction_count(env);
+    scan_page(
+        env,
+        count,
+        cursor,
+        limit,
+        |index| get_auction_index(env, index),
+        matches,
+    )
+}
+
+// Walk the auctions a user has bid on, in the order they first bid, the same way
+fn scan_user_bidding<F>(
+    env: &Env,
+    user: &Address,
+    cursor: u32,
+    limit: u32,
+    matches: F,
+) -> AuctionPage
+where
+    F: Fn(&Auction) -> bool,
+{
+    let user_auctions = get_user_auctions(env, &StorageKey::UserBidding(user.clone()));
+    scan_page(
+        env,
+        user_auctions.len(),
+        cursor,
+        limit,
+        |index| user_auctions.get(index),
+        matches,
+    )
+}
+
+// Shared page walk over `count` auction IDs looked up by position
+fn scan_page<L, F>(
+    env: &Env,
+    count: u32,
+    cursor: u32,
+    limit: u32,
+    lookup: L,
+    matches: F,
+) -> AuctionPage
+where
+    L: Fn(u32) -> Option<BytesN<32>>,
+    F: Fn(&Auction) -> bool,
+{
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let scan_end = count.min(cursor.saturating_add(MAX_SCAN));
+
+    let mut auction_ids = Vec::new(env);
+    let mut index = cursor;
+    while index < scan_end && auction_ids.len() < limit {
+        if let Some(auction_id) = lookup(index) {
+            if let Some(auction) = read_auction(env, &auction_id) {
+                if matches(&auction) {
+                    auction_ids.push_back(auction_id);
+                }
+            }
+        }
+        index += 1;
+    }
+
+    AuctionPage {
+        auction_ids,
+        next_cursor: index,
+        has_more: index < count,
+    }
+}
+
+// Allocation table of a cleared multi-unit auction
+pub fn query_allocations(
     env: &Env,
     auction_id: &BytesN<32>,
-    destination: &String,
-    shipping_speed: &u32,
-) -> i128 {
-    let auction = get_auction(env, auction_id);
+) -> Result<Vec<Allocation>, AuctionError> {
+    Ok(get_auction(env, auction_id)?.allocations)
+}
 
-    // Base cost
-    let mut cost: i128 = 500;
+// Current admin
+pub fn query_admin(env: &Env) -> Result<Address, AuctionError> {
+    get_admin(env)
+}
+
+// Registered product verifiers
+pub fn query_verifiers(env: &Env) -> Vec<Address> {
+    get_role_members(env, &StorageKey::Verifiers)
+}
 
-    // Add cost based on destination length (proxy for distance)
-    cost += destination.len() as i128 * 10;
+// Registered dispute resolvers
+pub fn query_resolvers(env: &Env) -> Vec<Address> {
+    get_role_members(env, &StorageKey::Resolvers)
+}
+
+// Registered carriers
+pub fn query_carriers(env: &Env) -> Vec<Address> {
+    get_role_members(env, &StorageKey::Carriers)
+}
 
-    // Add cost based on shipping speed
-    match shipping_speed {
-        1 => cost += 1000, // Express
-        2 => cost += 500,  // Standard
-        _ => cost += 200,  // Economy
+// Current platform fee configuration
+pub fn query_fee_config(env: &Env) -> FeeConfig {
+    get_fee_config(env)
+}
+
+// Positive attestations needed to authenticate a product
+pub fn query_attestation_quorum(env: &Env) -> u32 {
+    get_attestation_quorum(env)
+}
+
+// Total platform fees collected in a payment token
+pub fn query_total_fees(env: &Env, token: &Address) -> i128 {
+    get_fee_total(env, token)
+}
+
+// Dispute case file of an auction, with evidence and rulings
+pub fn query_dispute(env: &Env, auction_id: &BytesN<32>) -> Result<DisputeCase, AuctionError> {
+    let auction = get_auction(env, auction_id)?;
+
+    // Multi-unit winners each have their own case file
+    if auction.options.auction_type == AuctionType::MultiUnit {
+        return Err(AuctionError::UnsupportedAuctionType);
     }
+    get_dispute_case(env, auction_id, &auction.highest_bidder)
+}
 
-    // Apply bulk shipping discount if applicable
+// Dispute case file of one winner's allocation on a multi-unit auction
+pub fn query_allocation_dispute(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<DisputeCase, AuctionError> {
+    get_dispute_case(env, auction_id, buyer)
+}
+
+// Current visible price of an auction (hidden proxy maxima are never exposed)
+pub fn query_visible_price(env: &Env, auction_id: &BytesN<32>) -> Result<i128, AuctionError> {
+    let auction = get_auction(env, auction_id)?;
     if auction.has_highest_bid {
-        if auction.highest_bid_quantity > 1 {
-            // Apply a simple discount for bulk orders
-            // For each additional item, reduce cost by 10%
-            let discount_factor = 100 - ((auction.highest_bid_quantity - 1) * 10).min(50) as u32;
-            cost = cost * (discount_factor as i128) / 100;
+        Ok(auction.highest_bid_amount)
+    } else {
+        Ok(0)
+    }
+}
+
+// Current unit price of a Dutch auction
+pub fn query_current_price(env: &Env, auction_id: &BytesN<32>) -> Result<i128, AuctionError> {
+    let auction = get_auction(env, auction_id)?;
+
+    match &auction.options.auction_type {
+        AuctionType::Dutch(schedule) => {
+            Ok(schedule.price_at(auction.start_time, env.ledger().timestamp()))
         }
+        _ => Err(AuctionError::UnsupportedAuctionType),
     }
+}
+
+// Shipping cost an auction's buyer would be quoted for a zone and speed
+pub fn calculate_shipping_cost(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    zone: &String,
+    shipping_speed: &u32,
+) -> Result<i128, AuctionError> {
+    let auction = get_auction(env, auction_id)?;
+    shipping_cost_for(env, zone, *shipping_speed, auction.shipping_quantity())
+}
+
+// Shipping rate table (speed multipliers, quantity tiers and bulk discounts)
+pub fn query_shipping_rates(env: &Env) -> ShippingRates {
+    get_shipping_rates(env)
+}
+
+// Base shipping cost of a zone code
+pub fn query_shipping_zone(env: &Env, zone: &String) -> Option<i128> {
+    get_shipping_zone(env, zone)
+}
 
-    cost
+// Carriers a seller ships with (empty means any registered carrier)
+pub fn query_seller_carriers(env: &Env, seller: &Address) -> Vec<Address> {
+    get_seller_carriers(env, seller)
 }
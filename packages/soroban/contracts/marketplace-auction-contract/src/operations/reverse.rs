@@ -0,0 +1,172 @@
+use super::auction::{list_auction, next_auction_id, product_id};
+use super::bid::validate_bidding_open;
+use super::close_auction;
+use super::escrow::collect;
+use super::storage::{
+    add_to_user_selling, append_bid, get_auction, get_fee_config, get_payment_token, get_user_bid,
+    save_auction,
+};
+use crate::datatype::{
+    Auction, AuctionError, AuctionOptions, AuctionStatus, AuctionType, Bid, Product,
+    ProductCondition,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+// Post a service request that providers answer with offers within the budget.
+// The request is open for offers until the deadline
+pub fn create_service_request(
+    env: &Env,
+    requester: &Address,
+    subject: &String,
+    description: &String,
+    max_budget: &i128,
+    deadline: &u64,
+) -> Result<BytesN<32>, AuctionError> {
+    requester.require_auth();
+
+    let current_time = env.ledger().timestamp();
+    if *deadline <= current_time {
+        return Err(AuctionError::InvalidTimeRange);
+    }
+    if *max_budget <= 0 {
+        return Err(AuctionError::InvalidReservePrice);
+    }
+
+    let (counter, auction_id) = next_auction_id(env);
+
+    // The requester holds the seller role until an offer is accepted
+    let product = Product {
+        id: product_id(env, counter),
+        name: subject.clone(),
+        description: description.clone(),
+        condition: ProductCondition::New,
+        images: Vec::new(env),
+        seller: requester.clone(),
+        inventory_count: 1,
+        is_authenticated: false,
+        attestations: Vec::new(env),
+    };
+    let options = AuctionOptions {
+        auction_type: AuctionType::Reverse,
+        extension_window: 0,
+        extension_duration: 0,
+        max_extension: 0,
+        bid_increment: 0,
+        bid_increment_bps: 0,
+        starting_price: 0,
+        buy_now_price: 0,
+        buy_now_threshold: 0,
+        require_authentication: false,
+    };
+
+    // The budget is kept as the reserve price
+    let mut auction = Auction::new(
+        env,
+        auction_id,
+        product,
+        current_time,
+        *deadline,
+        *max_budget,
+        get_payment_token(env)?,
+        get_fee_config(env),
+        options,
+    );
+    auction.status = AuctionStatus::Active;
+
+    Ok(list_auction(env, counter, &auction))
+}
+
+// Offer to fulfil a service request for `amount`. A provider may only lower
+// their own earlier offer
+pub fn place_offer(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    provider: &Address,
+    amount: &i128,
+) -> Result<(), AuctionError> {
+    provider.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+    if auction.options.auction_type != AuctionType::Reverse {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    let current_time = validate_bidding_open(env, &auction)?;
+
+    if *provider == auction.product.seller {
+        return Err(AuctionError::Unauthorized);
+    }
+    if *amount <= 0 || *amount > auction.reserve_price {
+        return Err(AuctionError::InvalidAmount);
+    }
+    if let Some(previous) = get_user_bid(env, provider, auction_id) {
+        if *amount >= previous.amount {
+            return Err(AuctionError::BidTooLow);
+        }
+    }
+
+    // Offers are not escrowed; the requester pays once one is accepted
+    let offer = Bid {
+        bidder: provider.clone(),
+        amount: *amount,
+        timestamp: current_time,
+        quantity: 1,
+    };
+    append_bid(env, &mut auction, &offer);
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "offer_placed"), auction_id.clone()),
+        (auction_id.clone(), provider.clone(), *amount),
+    );
+
+    Ok(())
+}
+
+// Accept any provider's latest offer (requester only) until the deadline, the
+// same window in which offers can be placed. The requester escrows the offer
+// and becomes the buyer, the provider becomes the seller, and the request ends
+// so delivery, disputes and payout follow the usual flow
+pub fn accept_offer(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    provider: &Address,
+) -> Result<(), AuctionError> {
+    let mut auction = get_auction(env, auction_id)?;
+    if auction.options.auction_type != AuctionType::Reverse {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+
+    let requester = auction.product.seller.clone();
+    requester.require_auth();
+
+    if auction.status != AuctionStatus::Active {
+        return Err(AuctionError::AuctionNotActive);
+    }
+    // Past the deadline the request can only be ended unaccepted
+    if env.ledger().timestamp() > auction.end_time {
+        return Err(AuctionError::AuctionAlreadyEnded);
+    }
+
+    let offer = get_user_bid(env, provider, auction_id).ok_or(AuctionError::NoCommitment)?;
+    collect(env, &mut auction, &requester, offer.amount)?;
+
+    auction.set_highest_bid(Some(Bid {
+        bidder: requester.clone(),
+        amount: offer.amount,
+        timestamp: env.ledger().timestamp(),
+        quantity: 1,
+    }));
+    auction.product.seller = provider.clone();
+    add_to_user_selling(env, provider, auction_id);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "offer_accepted"), auction_id.clone()),
+        (auction_id.clone(), provider.clone(), offer.amount),
+    );
+
+    close_auction(env, &mut auction);
+
+    Ok(())
+}
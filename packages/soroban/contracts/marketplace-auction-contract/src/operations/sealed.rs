@@ -0,0 +1,236 @@
+use super::bid::require_authenticated;
+use super::escrow::{collect, refund, settle};
+use super::storage::{get_auction, get_sealed_bid, record_user_bid, save_auction, save_sealed_bid};
+use super::{close_auction, close_unsold_auction};
+use crate::datatype::{
+    Auction, AuctionError, AuctionStatus, AuctionType, Bid, SealedBid, SealedBidConfig,
+    SettlementKind,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Symbol};
+
+// Get the sealed-bid settings of an auction
+fn sealed_config(auction: &Auction) -> Result<SealedBidConfig, AuctionError> {
+    match &auction.options.auction_type {
+        AuctionType::Sealed(config) => Ok(config.clone()),
+        _ => Err(AuctionError::UnsupportedAuctionType),
+    }
+}
+
+// Submit a sealed bid commitment during the bidding phase
+pub fn commit_bid(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    bidder: &Address,
+    commitment: &BytesN<32>,
+) -> Result<(), AuctionError> {
+    bidder.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+    let config = sealed_config(&auction)?;
+
+    if auction.status != AuctionStatus::Active {
+        return Err(AuctionError::AuctionNotActive);
+    }
+    require_authenticated(env, &auction)?;
+
+    let current_time = env.ledger().timestamp();
+    if current_time > auction.end_time {
+        return Err(AuctionError::AuctionAlreadyEnded);
+    }
+
+    if get_sealed_bid(env, auction_id, bidder).is_some() {
+        return Err(AuctionError::AlreadyCommitted);
+    }
+
+    // The deposit is held until the bid is revealed
+    collect(env, &mut auction, bidder, config.deposit)?;
+    save_sealed_bid(
+        env,
+        auction_id,
+        &SealedBid {
+            bidder: bidder.clone(),
+            commitment: commitment.clone(),
+            timestamp: current_time,
+            index: auction.sealed.commitments,
+            revealed: false,
+            amount: 0,
+        },
+    );
+    auction.sealed.commitments += 1;
+    record_user_bid(env, bidder, auction_id, 0, current_time);
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "bid_committed"), auction_id.clone()),
+        (auction_id.clone(), bidder.clone()),
+    );
+
+    Ok(())
+}
+
+// Reveal a previously committed bid once bidding has closed, whether or not
+// the auction was moved to Revealing yet. Only the leading bid stays escrowed
+pub fn reveal_bid(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    bidder: &Address,
+    amount: &i128,
+    salt: &Bytes,
+) -> Result<(), AuctionError> {
+    bidder.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+    let config = sealed_config(&auction)?;
+
+    if auction.status != AuctionStatus::Active && auction.status != AuctionStatus::Revealing {
+        return Err(AuctionError::NotRevealing);
+    }
+    let current_time = env.ledger().timestamp();
+    if current_time <= auction.end_time {
+        return Err(AuctionError::NotRevealing);
+    }
+    if current_time > auction.end_time + config.reveal_duration {
+        return Err(AuctionError::RevealPhaseEnded);
+    }
+    if *amount <= 0 {
+        return Err(AuctionError::InvalidAmount);
+    }
+
+    let mut sealed_bid =
+        get_sealed_bid(env, auction_id, bidder).ok_or(AuctionError::NoCommitment)?;
+    if sealed_bid.revealed {
+        return Err(AuctionError::AlreadyRevealed);
+    }
+
+    // Check the revealed values against the commitment
+    let mut preimage = Bytes::from_slice(env, &amount.to_be_bytes());
+    preimage.append(salt);
+    let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+    if hash != sealed_bid.commitment {
+        return Err(AuctionError::CommitmentMismatch);
+    }
+
+    sealed_bid.revealed = true;
+    sealed_bid.amount = *amount;
+
+    // Escrow a bid that takes the lead and refund the one it overtakes; a bid
+    // below reserve or behind the leader is never escrowed
+    if *amount >= auction.reserve_price {
+        if takes_lead(env, &auction, &sealed_bid) {
+            collect(env, &mut auction, bidder, *amount)?;
+            if let Some(leader) = auction.highest_bid() {
+                auction.sealed.second_price = auction.sealed.second_price.max(leader.amount);
+                refund(env, &mut auction, &leader.bidder, leader.amount)?;
+            }
+            auction.set_highest_bid(Some(Bid {
+                bidder: bidder.clone(),
+                amount: *amount,
+                timestamp: sealed_bid.timestamp,
+                quantity: 1,
+            }));
+        } else {
+            auction.sealed.second_price = auction.sealed.second_price.max(*amount);
+        }
+    }
+
+    // Hand the deposit back
+    refund(env, &mut auction, bidder, config.deposit)?;
+    auction.sealed.reveals += 1;
+    save_sealed_bid(env, auction_id, &sealed_bid);
+    record_user_bid(env, bidder, auction_id, *amount, current_time);
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "bid_revealed"), auction_id.clone()),
+        (auction_id.clone(), bidder.clone(), *amount),
+    );
+
+    Ok(())
+}
+
+// Move a sealed-bid auction through its reveal phase: Active -> Revealing at
+// end_time, then -> Ended once the reveal phase is over. An auction nobody
+// moved to Revealing in time is settled straight away
+pub fn end_sealed_auction(env: &Env, auction_id: &BytesN<32>) -> Result<(), AuctionError> {
+    let mut auction = get_auction(env, auction_id)?;
+    let config = sealed_config(&auction)?;
+    let current_time = env.ledger().timestamp();
+
+    if current_time < auction.end_time {
+        return Err(AuctionError::AuctionNotEnded);
+    }
+
+    let reveal_end = auction.end_time + config.reveal_duration;
+    match auction.status {
+        AuctionStatus::Active if current_time <= reveal_end => {
+            auction.status = AuctionStatus::Revealing;
+            save_auction(env, auction_id, &auction);
+
+            // Emit event
+            env.events().publish(
+                (Symbol::new(env, "reveal_started"), auction_id.clone()),
+                (auction_id.clone(), reveal_end),
+            );
+        }
+        AuctionStatus::Active | AuctionStatus::Revealing => {
+            if current_time <= reveal_end {
+                return Err(AuctionError::RevealPhaseNotEnded);
+            }
+
+            settle_sealed_bids(env, &mut auction, &config)?;
+            if auction.reserve_met() {
+                close_auction(env, &mut auction);
+            } else {
+                close_unsold_auction(env, &mut auction);
+            }
+        }
+        _ => return Err(AuctionError::AuctionNotActive),
+    }
+
+    Ok(())
+}
+
+// Whether a revealed bid overtakes the current leader; on equal amounts the
+// earlier commitment wins
+fn takes_lead(env: &Env, auction: &Auction, sealed_bid: &SealedBid) -> bool {
+    let Some(leader) = auction.highest_bid() else {
+        return true;
+    };
+    if sealed_bid.amount != leader.amount {
+        return sealed_bid.amount > leader.amount;
+    }
+    get_sealed_bid(env, &auction.id, &leader.bidder)
+        .is_some_and(|leading| sealed_bid.index < leading.index)
+}
+
+// Charge the leader the second-highest price and forfeit unrevealed deposits
+// to the seller. Every other revealed bid was refunded when it was revealed
+fn settle_sealed_bids(
+    env: &Env,
+    auction: &mut Auction,
+    config: &SealedBidConfig,
+) -> Result<(), AuctionError> {
+    let unrevealed = auction.sealed.commitments - auction.sealed.reveals;
+    if unrevealed > 0 {
+        let seller = auction.product.seller.clone();
+        settle(
+            env,
+            auction,
+            &seller,
+            config.deposit * unrevealed as i128,
+            SettlementKind::ForfeitedDeposit,
+        )?;
+    }
+
+    // Winner only pays the second-highest price, or the reserve
+    if let Some(mut winner) = auction.highest_bid() {
+        let price = auction.sealed.second_price.max(auction.reserve_price);
+        refund(env, auction, &winner.bidder, winner.amount - price)?;
+        winner.amount = price;
+        auction.set_highest_bid(Some(winner));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,142 @@
-use super::storage::{get_auction, save_auction};
-use crate::datatype::{AuctionStatus, ShippingInfo, ShippingStatus};
-use soroban_sdk::{BytesN, Env, String, Symbol};
+use super::escrow::{collect, refund, release_to_seller};
+use super::storage::{
+    get_auction, get_dispute_window, get_lot, get_seller_carriers, get_shipping_rates,
+    get_shipping_zone, is_carrier, save_auction, save_lot, set_seller_carriers,
+};
+use crate::datatype::{
+    Auction, AuctionError, AuctionStatus, AuctionType, ShippingInfo, ShippingQuote, ShippingStatus,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
+
+// Choose the registered carriers a seller ships with (seller only).
+// An empty list accepts any registered carrier
+pub fn set_carriers_for_seller(
+    env: &Env,
+    seller: &Address,
+    carriers: &Vec<Address>,
+) -> Result<(), AuctionError> {
+    seller.require_auth();
+
+    for carrier in carriers.iter() {
+        if !is_carrier(env, &carrier) {
+            return Err(AuctionError::RoleNotAssigned);
+        }
+    }
+    set_seller_carriers(env, seller, carriers);
+
+    Ok(())
+}
+
+// Quote shipping from the admin rate table and escrow the quoted cost with
+// the winning bid (winning bidder only). Quoting again before the item ships
+// replaces the previous quote and refunds its cost
+pub fn quote_shipping(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+    zone: &String,
+    speed: &u32,
+    carrier: &Address,
+) -> Result<i128, AuctionError> {
+    buyer.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+
+    if auction.status != AuctionStatus::Ended || !auction.has_highest_bid {
+        return Err(AuctionError::AuctionNotSold);
+    }
+    if auction.highest_bidder != *buyer {
+        return Err(AuctionError::NotHighestBidder);
+    }
+    // Services are not shipped and multi-unit winners receive their units separately
+    if matches!(
+        auction.options.auction_type,
+        AuctionType::Reverse | AuctionType::MultiUnit
+    ) {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    if auction.shipping_status != ShippingStatus::NotShipped {
+        return Err(AuctionError::InvalidShippingTransition);
+    }
+
+    // The carrier must be registered and, if the seller chose carriers, one of them
+    let seller_carriers = get_seller_carriers(env, &auction.product.seller);
+    if !is_carrier(env, carrier)
+        || (!seller_carriers.is_empty() && !seller_carriers.contains(carrier))
+    {
+        return Err(AuctionError::Unauthorized);
+    }
+
+    let cost = shipping_cost_for(env, zone, *speed, auction.shipping_quantity())?;
+
+    if auction.has_shipping_quote {
+        let previous = auction.shipping_quote.cost;
+        refund(env, &mut auction, buyer, previous)?;
+    }
+    if cost > 0 {
+        collect(env, &mut auction, buyer, cost)?;
+    }
+
+    auction.has_shipping_quote = true;
+    auction.shipping_quote = ShippingQuote {
+        zone: zone.clone(),
+        speed: *speed,
+        carrier: carrier.clone(),
+        cost,
+        quoted_at: env.ledger().timestamp(),
+    };
+    auction.shipping_cost = cost;
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "shipping_quoted"), auction_id.clone()),
+        (
+            auction_id.clone(),
+            zone.clone(),
+            *speed,
+            carrier.clone(),
+            cost,
+        ),
+    );
+
+    Ok(cost)
+}
+
+// Shipping cost from the rate table: the zone base cost plus the per-unit
+// cost of the quantity tier reached, scaled by the speed multiplier, less the
+// bulk discount reached
+pub fn shipping_cost_for(
+    env: &Env,
+    zone: &String,
+    speed: u32,
+    quantity: u32,
+) -> Result<i128, AuctionError> {
+    let base_cost = get_shipping_zone(env, zone).ok_or(AuctionError::NoShippingInfo)?;
+    let rates = get_shipping_rates(env);
+    let multiplier_bps = rates
+        .speed_multipliers
+        .get(speed)
+        .ok_or(AuctionError::NoShippingInfo)?;
+
+    let unit_cost = rates
+        .quantity_tiers
+        .iter()
+        .filter(|tier| tier.min_quantity <= quantity)
+        .last()
+        .map(|tier| tier.unit_cost)
+        .unwrap_or(0);
+    let discount_bps = rates
+        .bulk_discounts
+        .iter()
+        .filter(|discount| discount.min_quantity <= quantity)
+        .last()
+        .map(|discount| discount.discount_bps)
+        .unwrap_or(0);
+
+    let cost = (base_cost + unit_cost * quantity as i128) * multiplier_bps as i128 / 10_000;
+    Ok(cost - cost * discount_bps as i128 / 10_000)
+}
 
 // Add shipping information
 pub fn add_shipping_info(
@@ -9,20 +145,26 @@ pub fn add_shipping_info(
     tracking_number: &String,
     carrier: &String,
     estimated_delivery: &u64,
-    shipping_cost: &i128,
     recipient_address: &String,
-) {
-    let mut auction = get_auction(env, auction_id);
+) -> Result<(), AuctionError> {
+    let mut auction = get_auction(env, auction_id)?;
 
     // Only the seller can add shipping info
     auction.product.seller.require_auth();
 
     if auction.status != AuctionStatus::Ended {
-        panic!("Can only add shipping info for ended auctions");
+        return Err(AuctionError::AuctionNotSold);
     }
 
     if !auction.has_highest_bid {
-        panic!("No winning bid for this auction");
+        return Err(AuctionError::AuctionNotSold);
+    }
+
+    validate_transition(auction.shipping_status, ShippingStatus::Shipped)?;
+
+    // The buyer must have paid for a shipping quote first
+    if !auction.has_shipping_quote {
+        return Err(AuctionError::NoShippingInfo);
     }
 
     // Create shipping info
@@ -31,44 +173,190 @@ pub fn add_shipping_info(
         tracking_number: tracking_number.clone(),
         carrier: carrier.clone(),
         estimated_delivery: *estimated_delivery,
-        shipping_cost: *shipping_cost,
+        shipping_cost: auction.shipping_quote.cost,
         recipient_address: recipient_address.clone(),
     };
 
     auction.set_shipping_info(Some(shipping_info), env);
     save_auction(env, auction_id, &auction);
 
+    // Every product of a lot leaves in this shipment
+    if auction.lot_size > 0 {
+        let mut lot = get_lot(env, auction_id);
+        for index in 0..lot.len() {
+            let mut item = lot.get(index).unwrap();
+            item.shipping_status = ShippingStatus::Shipped;
+            lot.set(index, item);
+        }
+        save_lot(env, auction_id, &lot);
+    }
+
     // Emit event
     env.events().publish(
         (Symbol::new(env, "product_shipped"), auction_id.clone()),
         (auction_id.clone(), tracking_number.clone()),
     );
+
+    Ok(())
 }
 
-// Update shipping status
-pub fn update_shipping_status(env: &Env, auction_id: &BytesN<32>, new_status: &ShippingStatus) {
-    let mut auction = get_auction(env, auction_id);
+// Update shipping status. The seller or the quoted carrier can report
+// progress, but only the carrier can report delivery
+pub fn update_shipping_status(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    caller: &Address,
+    new_status: &ShippingStatus,
+) -> Result<(), AuctionError> {
+    caller.require_auth();
 
-    // Only the seller can update shipping status
-    auction.product.seller.require_auth();
+    let mut auction = get_auction(env, auction_id)?;
 
-    if !auction.has_shipping {
-        panic!("No shipping information available");
+    // Lot items report their progress one by one
+    if auction.lot_size > 0 || auction.options.auction_type == AuctionType::MultiUnit {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    // A disputed or completed sale no longer takes shipping updates
+    if auction.status != AuctionStatus::Ended {
+        return Err(AuctionError::AuctionNotSold);
     }
+    check_reporter(env, &auction, caller, *new_status)?;
 
-    // Update the shipping status
-    auction.shipping_status = *new_status;
+    if !auction.has_shipping {
+        return Err(AuctionError::NoShippingInfo);
+    }
+    validate_transition(auction.shipping_status, *new_status)?;
 
-    // If delivered, update auction status
     if *new_status == ShippingStatus::Delivered {
-        auction.status = AuctionStatus::Completed;
+        mark_delivered(env, &mut auction);
+    } else {
+        auction.shipping_status = *new_status;
+    }
 
-        // Emit delivery event
-        env.events().publish(
-            (Symbol::new(env, "product_delivered"), auction_id.clone()),
-            auction_id.clone(),
-        );
+    save_auction(env, auction_id, &auction);
+
+    Ok(())
+}
+
+// Confirm delivery as the winning bidder
+pub fn confirm_delivery(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<(), AuctionError> {
+    buyer.require_auth();
+
+    let mut auction = get_auction(env, auction_id)?;
+
+    if auction.lot_size > 0 || auction.options.auction_type == AuctionType::MultiUnit {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    if !auction.has_highest_bid || auction.highest_bidder != *buyer {
+        return Err(AuctionError::NotHighestBidder);
+    }
+    if auction.status != AuctionStatus::Ended {
+        return Err(AuctionError::AuctionNotSold);
+    }
+
+    // A service is delivered without shipping; anything else must have shipped
+    if auction.options.auction_type == AuctionType::Reverse {
+        if auction.shipping_status == ShippingStatus::Delivered {
+            return Err(AuctionError::InvalidShippingTransition);
+        }
+    } else {
+        if !auction.has_shipping {
+            return Err(AuctionError::NoShippingInfo);
+        }
+        validate_transition(auction.shipping_status, ShippingStatus::Delivered)?;
     }
 
+    mark_delivered(env, &mut auction);
     save_auction(env, auction_id, &auction);
+
+    Ok(())
+}
+
+// Complete a delivered auction once its dispute window has passed without a
+// dispute, paying the seller out of escrow. Callable by anyone
+pub fn finalize_delivery(env: &Env, auction_id: &BytesN<32>) -> Result<(), AuctionError> {
+    let mut auction = get_auction(env, auction_id)?;
+
+    // Multi-unit winners are paid out one allocation at a time
+    if auction.options.auction_type == AuctionType::MultiUnit {
+        return Err(AuctionError::UnsupportedAuctionType);
+    }
+    if auction.status != AuctionStatus::Ended {
+        return Err(AuctionError::AuctionNotSold);
+    }
+    if auction.shipping_status != ShippingStatus::Delivered {
+        return Err(AuctionError::NotDelivered);
+    }
+    if env.ledger().timestamp() <= auction.dispute_deadline {
+        return Err(AuctionError::DisputeWindowOpen);
+    }
+
+    auction.status = AuctionStatus::Completed;
+    release_to_seller(env, &mut auction)?;
+    save_auction(env, auction_id, &auction);
+
+    // Emit event
+    env.events().publish(
+        (Symbol::new(env, "auction_completed"), auction_id.clone()),
+        auction_id.clone(),
+    );
+
+    Ok(())
+}
+
+// Check that the caller may report shipping progress: the seller or the quoted
+// carrier, while it is still registered. Only the carrier can report delivery
+pub(super) fn check_reporter(
+    env: &Env,
+    auction: &Auction,
+    caller: &Address,
+    new_status: ShippingStatus,
+) -> Result<(), AuctionError> {
+    let caller_is_carrier = *caller == auction.shipping_quote.carrier && is_carrier(env, caller);
+    if *caller != auction.product.seller && !caller_is_carrier {
+        return Err(AuctionError::Unauthorized);
+    }
+    if new_status == ShippingStatus::Delivered && !caller_is_carrier {
+        return Err(AuctionError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+// Record delivery and open the buyer's dispute window
+pub(super) fn mark_delivered(env: &Env, auction: &mut Auction) {
+    let now = env.ledger().timestamp();
+    auction.shipping_status = ShippingStatus::Delivered;
+    auction.delivered_at = now;
+    auction.dispute_deadline = now + get_dispute_window(env);
+
+    // Emit delivery event
+    env.events().publish(
+        (Symbol::new(env, "product_delivered"), auction.id.clone()),
+        (auction.id.clone(), auction.dispute_deadline),
+    );
+}
+
+// Shipping only moves forward: NotShipped -> Shipped -> InTransit -> Delivered.
+// InTransit may be skipped, but no status can be repeated or undone
+pub(super) fn validate_transition(
+    from: ShippingStatus,
+    to: ShippingStatus,
+) -> Result<(), AuctionError> {
+    let allowed = matches!(
+        (from, to),
+        (ShippingStatus::NotShipped, ShippingStatus::Shipped)
+            | (ShippingStatus::Shipped, ShippingStatus::InTransit)
+            | (ShippingStatus::Shipped, ShippingStatus::Delivered)
+            | (ShippingStatus::InTransit, ShippingStatus::Delivered)
+    );
+    if !allowed {
+        return Err(AuctionError::InvalidShippingTransition);
+    }
+
+    Ok(())
 }
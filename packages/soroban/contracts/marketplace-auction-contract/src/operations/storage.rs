@@ -1,65 +1,262 @@
-use crate::datatype::{Auction, StorageKey};
-use soroban_sdk::{Address, BytesN, Env, Map, Vec};
+use crate::datatype::{
+    Auction, AuctionError, Bid, DisputeCase, FeeConfig, LotItem, ProxyBid, SealedBid,
+    ShippingRates, StorageKey, UserBid,
+};
+use soroban_sdk::{Address, BytesN, Env, Map, String, Vec};
+
+// TTL management (in ledgers, ~5 seconds each)
+const DAY_IN_LEDGERS: u32 = 17_280;
+const INSTANCE_TTL_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+// Dispute window after delivery unless the admin configures another
+const DEFAULT_DISPUTE_WINDOW: u64 = 3 * 24 * 60 * 60;
+
+// Time the seller has to answer a dispute, and to appeal a ruling, unless configured
+const DEFAULT_RESPONSE_WINDOW: u64 = 3 * 24 * 60 * 60;
+const DEFAULT_APPEAL_WINDOW: u64 = 2 * 24 * 60 * 60;
+
+// Positive attestations a product needs unless the admin configures another quorum
+const DEFAULT_ATTESTATION_QUORUM: u32 = 1;
+
+// Number of bids stored per bid page entry
+pub const BID_PAGE_SIZE: u32 = 50;
+
+// Keep contract instance data (admin, roles, counters) alive
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+// Keep a persistent entry alive
+fn extend_persistent_ttl(env: &Env, key: &StorageKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
 
 // Get admin address
-pub fn get_admin(env: &Env) -> Address {
-    env.storage().instance().get(&StorageKey::Admin).unwrap()
+pub fn get_admin(env: &Env) -> Result<Address, AuctionError> {
+    env.storage()
+        .instance()
+        .get(&StorageKey::Admin)
+        .ok_or(AuctionError::NotInitialized)
+}
+
+// Get the token used to escrow bids
+pub fn get_payment_token(env: &Env) -> Result<Address, AuctionError> {
+    env.storage()
+        .instance()
+        .get(&StorageKey::PaymentToken)
+        .ok_or(AuctionError::NotInitialized)
+}
+
+// Get the addresses holding a role (Verifiers, Resolvers or Carriers)
+pub fn get_role_members(env: &Env, role: &StorageKey) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(role)
+        .unwrap_or_else(|| Vec::new(env))
 }
 
 // Check if a user is a verifier
 pub fn is_verifier(env: &Env, address: &Address) -> bool {
-    let verifiers: Vec<Address> = env
-        .storage()
+    get_role_members(env, &StorageKey::Verifiers).contains(address)
+}
+
+// Check if a user is a registered carrier
+pub fn is_carrier(env: &Env, address: &Address) -> bool {
+    get_role_members(env, &StorageKey::Carriers).contains(address)
+}
+
+// Positive attestations needed for a product to count as authenticated
+pub fn get_attestation_quorum(env: &Env) -> u32 {
+    env.storage()
         .instance()
-        .get(&StorageKey::Verifiers)
-        .unwrap_or_else(|| Vec::new(env));
-    verifiers.contains(address)
+        .get(&StorageKey::AttestationQuorum)
+        .unwrap_or(DEFAULT_ATTESTATION_QUORUM)
 }
 
-// Check if a user is a resolver
-pub fn is_resolver(env: &Env, address: &Address) -> bool {
-    let resolvers: Vec<Address> = env
-        .storage()
+// Seconds the buyer has to dispute after delivery
+pub fn get_dispute_window(env: &Env) -> u64 {
+    env.storage()
         .instance()
-        .get(&StorageKey::Resolvers)
-        .unwrap_or_else(|| Vec::new(env));
-    resolvers.contains(address)
+        .get(&StorageKey::DisputeWindow)
+        .unwrap_or(DEFAULT_DISPUTE_WINDOW)
 }
 
-// Get auction by ID
-pub fn get_auction(env: &Env, auction_id: &BytesN<32>) -> Auction {
-    let auctions: Map<BytesN<32>, Auction> = env
-        .storage()
+// Seconds the seller has to respond to a dispute
+pub fn get_response_window(env: &Env) -> u64 {
+    env.storage()
         .instance()
-        .get(&StorageKey::Auctions)
-        .unwrap_or_else(|| Map::<BytesN<32>, Auction>::new(env));
+        .get(&StorageKey::ResponseWindow)
+        .unwrap_or(DEFAULT_RESPONSE_WINDOW)
+}
 
-    auctions
-        .get(auction_id.clone())
-        .unwrap_or_else(|| panic!("Auction not found"))
+// Seconds either party has to appeal a ruling
+pub fn get_appeal_window(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::AppealWindow)
+        .unwrap_or(DEFAULT_APPEAL_WINDOW)
 }
-// Save auction
-pub fn save_auction(env: &Env, auction_id: &BytesN<32>, auction: &Auction) {
-    let mut auctions: Map<BytesN<32>, Auction> = env
-        .storage()
+
+// Base shipping cost of a zone, if the admin has configured it
+pub fn get_shipping_zone(env: &Env, zone: &String) -> Option<i128> {
+    let key = StorageKey::ShippingZone(zone.clone());
+    let base_cost: Option<i128> = env.storage().persistent().get(&key);
+    if base_cost.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    base_cost
+}
+
+// Set the base shipping cost of a zone
+pub fn set_shipping_zone(env: &Env, zone: &String, base_cost: i128) {
+    let key = StorageKey::ShippingZone(zone.clone());
+    env.storage().persistent().set(&key, &base_cost);
+    extend_persistent_ttl(env, &key);
+}
+
+// Remove a shipping zone
+pub fn remove_shipping_zone(env: &Env, zone: &String) {
+    env.storage()
+        .persistent()
+        .remove(&StorageKey::ShippingZone(zone.clone()));
+}
+
+// Shipping rate table; empty (no speeds) until the admin configures one
+pub fn get_shipping_rates(env: &Env) -> ShippingRates {
+    env.storage()
         .instance()
-        .get(&StorageKey::Auctions)
-        .unwrap_or_else(|| Map::<BytesN<32>, Auction>::new(env));
+        .get(&StorageKey::ShippingRates)
+        .unwrap_or_else(|| ShippingRates {
+            speed_multipliers: Map::new(env),
+            quantity_tiers: Vec::new(env),
+            bulk_discounts: Vec::new(env),
+        })
+}
 
-    auctions.set(auction_id.clone(), auction.clone());
+// Carriers a seller ships with; empty means any registered carrier
+pub fn get_seller_carriers(env: &Env, seller: &Address) -> Vec<Address> {
+    let key = StorageKey::SellerCarriers(seller.clone());
+    let carriers: Option<Vec<Address>> = env.storage().persistent().get(&key);
+    match carriers {
+        Some(carriers) => {
+            extend_persistent_ttl(env, &key);
+            carriers
+        }
+        None => Vec::new(env),
+    }
+}
 
+// Set the carriers a seller ships with
+pub fn set_seller_carriers(env: &Env, seller: &Address, carriers: &Vec<Address>) {
+    let key = StorageKey::SellerCarriers(seller.clone());
+    env.storage().persistent().set(&key, carriers);
+    extend_persistent_ttl(env, &key);
+}
+
+// Current platform fee; no fee until the admin configures one
+pub fn get_fee_config(env: &Env) -> FeeConfig {
     env.storage()
         .instance()
-        .set(&StorageKey::Auctions, &auctions);
+        .get(&StorageKey::FeeConfig)
+        .unwrap_or_else(|| FeeConfig {
+            fee_bps: 0,
+            flat_fee: 0,
+            recipient: env.current_contract_address(),
+        })
+}
+
+// Total platform fees collected in a payment token
+pub fn get_fee_total(env: &Env, token: &Address) -> i128 {
+    let key = StorageKey::FeeTotal(token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+// Add to the platform fees collected in a payment token
+pub fn add_fee_total(env: &Env, token: &Address, amount: i128) {
+    let key = StorageKey::FeeTotal(token.clone());
+    let total = get_fee_total(env, token) + amount;
+    env.storage().persistent().set(&key, &total);
+    extend_persistent_ttl(env, &key);
+}
+
+// Check if a user is a resolver
+pub fn is_resolver(env: &Env, address: &Address) -> bool {
+    get_role_members(env, &StorageKey::Resolvers).contains(address)
+}
+
+// Get auction by ID if it exists
+pub fn try_get_auction(env: &Env, auction_id: &BytesN<32>) -> Option<Auction> {
+    let key = StorageKey::Auction(auction_id.clone());
+    let auction: Option<Auction> = env.storage().persistent().get(&key);
+    if auction.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    auction
+}
+
+// Get auction by ID
+pub fn get_auction(env: &Env, auction_id: &BytesN<32>) -> Result<Auction, AuctionError> {
+    try_get_auction(env, auction_id).ok_or(AuctionError::AuctionNotFound)
+}
+
+// Save auction
+pub fn save_auction(env: &Env, auction_id: &BytesN<32>, auction: &Auction) {
+    let key = StorageKey::Auction(auction_id.clone());
+    env.storage().persistent().set(&key, auction);
+    extend_persistent_ttl(env, &key);
+    extend_instance_ttl(env);
+}
+
+// Get the dispute case file of a buyer's purchase from an auction
+pub fn get_dispute_case(
+    env: &Env,
+    auction_id: &BytesN<32>,
+    buyer: &Address,
+) -> Result<DisputeCase, AuctionError> {
+    let key = StorageKey::DisputeCase(auction_id.clone(), buyer.clone());
+    let case: Option<DisputeCase> = env.storage().persistent().get(&key);
+    if case.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    case.ok_or(AuctionError::NoOpenDispute)
+}
+
+// Save the dispute case file of a buyer's purchase from an auction
+pub fn save_dispute_case(env: &Env, auction_id: &BytesN<32>, case: &DisputeCase) {
+    let key = StorageKey::DisputeCase(auction_id.clone(), case.buyer.clone());
+    env.storage().persistent().set(&key, case);
+    extend_persistent_ttl(env, &key);
+}
+
+// Get the products of a lot auction
+pub fn get_lot(env: &Env, auction_id: &BytesN<32>) -> Vec<LotItem> {
+    let key = StorageKey::Lot(auction_id.clone());
+    match env.storage().persistent().get(&key) {
+        Some(lot) => {
+            extend_persistent_ttl(env, &key);
+            lot
+        }
+        None => Vec::new(env),
+    }
+}
+
+// Save the products of a lot auction
+pub fn save_lot(env: &Env, auction_id: &BytesN<32>, lot: &Vec<LotItem>) {
+    let key = StorageKey::Lot(auction_id.clone());
+    env.storage().persistent().set(&key, lot);
+    extend_persistent_ttl(env, &key);
 }
 
 // Get the next auction counter value
 pub fn get_and_increment_auction_counter(env: &Env) -> u32 {
-    let counter: u32 = env
-        .storage()
-        .instance()
-        .get(&StorageKey::AuctionCounter)
-        .unwrap_or(0);
+    let counter = get_auction_count(env);
 
     let new_counter = counter + 1;
     env.storage()
@@ -69,31 +266,184 @@ pub fn get_and_increment_auction_counter(env: &Env) -> u32 {
     counter
 }
 
+// Number of auctions ever created
+pub fn get_auction_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&StorageKey::AuctionCounter)
+        .unwrap_or(0)
+}
+
+// Record the auction ID created at a given counter value, for paginated discovery
+pub fn set_auction_index(env: &Env, index: u32, auction_id: &BytesN<32>) {
+    let key = StorageKey::AuctionIndex(index);
+    env.storage().persistent().set(&key, auction_id);
+    extend_persistent_ttl(env, &key);
+}
+
+// Get the auction ID created at a given counter value
+pub fn get_auction_index(env: &Env, index: u32) -> Option<BytesN<32>> {
+    let key = StorageKey::AuctionIndex(index);
+    let auction_id: Option<BytesN<32>> = env.storage().persistent().get(&key);
+    if auction_id.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    auction_id
+}
+
+// Append a bid to the auction's paged bid history and the bidder's own record
+pub fn append_bid(env: &Env, auction: &mut Auction, bid: &Bid) {
+    record_user_bid(env, &bid.bidder, &auction.id, bid.amount, bid.timestamp);
+
+    let key = StorageKey::BidPage(auction.id.clone(), auction.bid_count / BID_PAGE_SIZE);
+    let mut page: Vec<Bid> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+
+    page.push_back(bid.clone());
+    env.storage().persistent().set(&key, &page);
+    extend_persistent_ttl(env, &key);
+
+    auction.bid_count += 1;
+}
+
+// Get one page of an auction's bid history
+pub fn get_bid_page(env: &Env, auction_id: &BytesN<32>, page: u32) -> Vec<Bid> {
+    let key = StorageKey::BidPage(auction_id.clone(), page);
+    let bids: Option<Vec<Bid>> = env.storage().persistent().get(&key);
+    match bids {
+        Some(bids) => {
+            extend_persistent_ttl(env, &key);
+            bids
+        }
+        None => Vec::new(env),
+    }
+}
+
+// Get an auction's full bid history
+pub fn get_all_bids(env: &Env, auction: &Auction) -> Vec<Bid> {
+    let mut bids = Vec::new(env);
+    let pages = auction.bid_count.div_ceil(BID_PAGE_SIZE);
+    for page in 0..pages {
+        bids.append(&get_bid_page(env, &auction.id, page));
+    }
+    bids
+}
+
+// Get a bidder's sealed commitment on an auction
+pub fn get_sealed_bid(env: &Env, auction_id: &BytesN<32>, bidder: &Address) -> Option<SealedBid> {
+    let key = StorageKey::SealedBid(auction_id.clone(), bidder.clone());
+    let sealed_bid: Option<SealedBid> = env.storage().persistent().get(&key);
+    if sealed_bid.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    sealed_bid
+}
+
+// Save a bidder's sealed commitment on an auction
+pub fn save_sealed_bid(env: &Env, auction_id: &BytesN<32>, sealed_bid: &SealedBid) {
+    let key = StorageKey::SealedBid(auction_id.clone(), sealed_bid.bidder.clone());
+    env.storage().persistent().set(&key, sealed_bid);
+    extend_persistent_ttl(env, &key);
+}
+
+// Get a bidder's hidden proxy bid on an auction
+pub fn get_proxy_bid(env: &Env, auction_id: &BytesN<32>, bidder: &Address) -> Option<ProxyBid> {
+    let key = StorageKey::ProxyBid(auction_id.clone(), bidder.clone());
+    env.storage().persistent().get(&key)
+}
+
+// Save a bidder's hidden proxy bid on an auction
+pub fn set_proxy_bid(env: &Env, auction_id: &BytesN<32>, proxy: &ProxyBid) {
+    let key = StorageKey::ProxyBid(auction_id.clone(), proxy.bidder.clone());
+    env.storage().persistent().set(&key, proxy);
+    extend_persistent_ttl(env, &key);
+}
+
+// Remove a bidder's proxy bid once it is outbid or settled
+pub fn remove_proxy_bid(env: &Env, auction_id: &BytesN<32>, bidder: &Address) {
+    let key = StorageKey::ProxyBid(auction_id.clone(), bidder.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// Get the auctions listed under a per-user key
+pub fn get_user_auctions(env: &Env, user_key: &StorageKey) -> Vec<BytesN<32>> {
+    let user_auctions: Option<Vec<BytesN<32>>> = env.storage().persistent().get(user_key);
+    match user_auctions {
+        Some(user_auctions) => {
+            extend_persistent_ttl(env, user_key);
+            user_auctions
+        }
+        None => Vec::new(env),
+    }
+}
+
 // Add auction to user's selling list
 pub fn add_to_user_selling(env: &Env, seller: &Address, auction_id: &BytesN<32>) {
     let user_key = StorageKey::UserSelling(seller.clone());
-    let mut user_auctions: Vec<BytesN<32>> = env
-        .storage()
-        .instance()
-        .get(&user_key)
-        .unwrap_or_else(|| Vec::new(env));
+    let mut user_auctions = get_user_auctions(env, &user_key);
 
     user_auctions.push_back(auction_id.clone());
-    env.storage().instance().set(&user_key, &user_auctions);
+    env.storage().persistent().set(&user_key, &user_auctions);
+    extend_persistent_ttl(env, &user_key);
+}
+
+// Get every auction that listed a product, oldest first
+pub fn get_product_lineage(env: &Env, product_id: &BytesN<32>) -> Vec<BytesN<32>> {
+    get_user_auctions(env, &StorageKey::ProductLineage(product_id.clone()))
+}
+
+// Add an auction to its product's listing history
+pub fn add_to_product_lineage(env: &Env, product_id: &BytesN<32>, auction_id: &BytesN<32>) {
+    let key = StorageKey::ProductLineage(product_id.clone());
+    let mut lineage = get_user_auctions(env, &key);
+
+    lineage.push_back(auction_id.clone());
+    env.storage().persistent().set(&key, &lineage);
+    extend_persistent_ttl(env, &key);
+}
+
+// Record a bidder's latest bid on an auction and index the auction under them
+pub fn record_user_bid(
+    env: &Env,
+    bidder: &Address,
+    auction_id: &BytesN<32>,
+    amount: i128,
+    timestamp: u64,
+) {
+    add_to_user_bidding(env, bidder, auction_id);
+
+    let key = StorageKey::UserBid(bidder.clone(), auction_id.clone());
+    let record = UserBid {
+        auction_id: auction_id.clone(),
+        amount,
+        timestamp,
+        is_winning: false,
+    };
+    env.storage().persistent().set(&key, &record);
+    extend_persistent_ttl(env, &key);
+}
+
+// Get a bidder's latest bid on an auction
+pub fn get_user_bid(env: &Env, bidder: &Address, auction_id: &BytesN<32>) -> Option<UserBid> {
+    let key = StorageKey::UserBid(bidder.clone(), auction_id.clone());
+    let record: Option<UserBid> = env.storage().persistent().get(&key);
+    if record.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    record
 }
 
 // Add auction to user's bidding list
-#[allow(dead_code)]
 pub fn add_to_user_bidding(env: &Env, bidder: &Address, auction_id: &BytesN<32>) {
     let user_key = StorageKey::UserBidding(bidder.clone());
-    let mut user_auctions: Vec<BytesN<32>> = env
-        .storage()
-        .instance()
-        .get(&user_key)
-        .unwrap_or_else(|| Vec::new(env));
+    let mut user_auctions = get_user_auctions(env, &user_key);
 
     if !user_auctions.contains(auction_id) {
         user_auctions.push_back(auction_id.clone());
-        env.storage().instance().set(&user_key, &user_auctions);
+        env.storage().persistent().set(&user_key, &user_auctions);
+        extend_persistent_ttl(env, &user_key);
     }
 }
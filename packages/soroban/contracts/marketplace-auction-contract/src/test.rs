@@ -1,11 +1,14 @@
 #![cfg(test)]
 
 use crate::{
-    AuctionContract, AuctionContractClient, DisputeStatus, ProductCondition, ShippingStatus,
+    AuctionContract, AuctionContractClient, AuctionError, AuctionOptions, AuctionType,
+    BulkDiscount, DisputeResolution, DisputeStatus, DutchSchedule, FeeConfig, LotItemInput,
+    ProductCondition, QuantityTier, SealedBidConfig, SettlementKind, ShippingRates, ShippingStatus,
 };
 use soroban_sdk::{
+    map,
     testutils::{Address as _, Ledger as _},
-    vec, Address, BytesN, Env, String,
+    token, vec, Address, Bytes, BytesN, Env, String,
 };
 
 // Helper function to create a standard test environment
@@ -24,18 +27,88 @@ fn setup_test() -> (
     let seller = Address::generate(&env);
     let bidder = Address::generate(&env);
 
+    // Payment token for bid escrow
+    let token = env.register_stellar_asset_contract_v2(admin.clone());
+
     // Initialize the contract
     env.mock_all_auths();
-    client.initialize(&admin);
+    client.initialize(&admin, &token.address());
+    fund(&env, &client, &bidder, 1_000_000);
 
     (env, client, admin, seller, bidder)
 }
 
+// Helper function to mint payment tokens to a test account
+fn fund(env: &Env, client: &AuctionContractClient<'_>, to: &Address, amount: i128) {
+    let token = token::StellarAssetClient::new(env, &payment_token(env, client));
+    env.mock_all_auths();
+    token.mint(to, &amount);
+}
+
+// Helper function to read a payment token balance
+fn balance(env: &Env, client: &AuctionContractClient<'_>, of: &Address) -> i128 {
+    token::Client::new(env, &payment_token(env, client)).balance(of)
+}
+
+// Helper function to register a carrier and a free "LOCAL" zone shipped at speed 1
+fn setup_free_shipping(env: &Env, client: &AuctionContractClient<'_>, admin: &Address) -> Address {
+    let carrier = Address::generate(env);
+    env.mock_all_auths();
+    client.add_carrier(admin, &carrier);
+    env.mock_all_auths();
+    client.set_shipping_zone(admin, &String::from_str(env, "LOCAL"), &0);
+    env.mock_all_auths();
+    client.set_shipping_rates(
+        admin,
+        &ShippingRates {
+            speed_multipliers: map![env, (1, 10_000)],
+            quantity_tiers: vec![env],
+            bulk_discounts: vec![env],
+        },
+    );
+    carrier
+}
+
+fn payment_token(env: &Env, client: &AuctionContractClient<'_>) -> Address {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .get(&crate::datatype::StorageKey::PaymentToken)
+            .unwrap()
+    })
+}
+
+// Helper function to build auction options with every optional feature disabled
+fn default_options() -> AuctionOptions {
+    AuctionOptions {
+        auction_type: AuctionType::English,
+        extension_window: 0,
+        extension_duration: 0,
+        max_extension: 0,
+        bid_increment: 0,
+        bid_increment_bps: 0,
+        starting_price: 0,
+        buy_now_price: 0,
+        buy_now_threshold: 0,
+        require_authentication: false,
+    }
+}
+
 // Helper function to create a test auction
 fn create_test_auction(
     env: &Env,
     client: &AuctionContractClient<'_>,
     seller: &Address,
+) -> BytesN<32> {
+    create_test_auction_with_options(env, client, seller, &default_options())
+}
+
+// Helper function to create a test auction with custom options
+fn create_test_auction_with_options(
+    env: &Env,
+    client: &AuctionContractClient<'_>,
+    seller: &Address,
+    options: &AuctionOptions,
 ) -> BytesN<32> {
     let name = String::from_str(env, "Test Item");
     let description = String::from_str(env, "A test auction item");
@@ -63,6 +136,7 @@ fn create_test_auction(
         &1000,        // &i128
         &start_time,  // &u64
         &end_time,    // &u64
+        options,      // &AuctionOptions
     )
 }
 
@@ -74,15 +148,71 @@ fn test_initialize() {
     let client = AuctionContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
+    let token = Address::generate(&env);
 
     // Initialize the contract
     env.mock_all_auths();
-    client.initialize(&admin);
+    client.initialize(&admin, &token);
 
     // Try to initialize again - should fail
     env.mock_all_auths();
-    let result = client.try_initialize(&admin);
-    assert!(result.is_err());
+    let result = client.try_initialize(&admin, &token);
+    assert_eq!(result, Err(Ok(AuctionError::AlreadyInitialized)));
+}
+
+// Test role removal, listing and the two-step admin handover
+#[test]
+fn test_role_management() {
+    let (env, client, admin, _seller, _bidder) = setup_test();
+    let verifier = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.add_verifier(&admin, &verifier);
+    env.mock_all_auths();
+    client.add_resolver(&admin, &resolver);
+    assert_eq!(client.get_verifiers(), vec![&env, verifier.clone()]);
+    assert_eq!(client.get_resolvers(), vec![&env, resolver.clone()]);
+
+    env.mock_all_auths();
+    client.remove_verifier(&admin, &verifier);
+    assert_eq!(client.get_verifiers().len(), 0);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_remove_verifier(&admin, &verifier),
+        Err(Ok(AuctionError::RoleNotAssigned))
+    );
+
+    // Only the proposed admin can accept, and only once proposed
+    let new_admin = Address::generate(&env);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(AuctionError::NoPendingAdmin))
+    );
+    env.mock_all_auths();
+    client.propose_admin(&admin, &new_admin);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_accept_admin(&resolver),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+
+    // The old admin keeps control until the handover is accepted
+    env.mock_all_auths();
+    client.remove_resolver(&admin, &resolver);
+    env.mock_all_auths();
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_add_verifier(&admin, &verifier),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    env.mock_all_auths();
+    client.add_verifier(&new_admin, &verifier);
+    assert_eq!(client.get_verifiers(), vec![&env, verifier]);
 }
 
 // Test auction lifecycle
@@ -90,6 +220,7 @@ fn test_initialize() {
 fn test_auction_lifecycle() {
     let (env, client, admin, seller, bidder1) = setup_test();
     let bidder2 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
 
     // Create verifier
     let verifier = Address::generate(&env);
@@ -134,40 +265,416 @@ fn test_auction_lifecycle() {
 
     // Verify product authenticity
     env.mock_all_auths();
-    client.verify_product(&verifier, &auction_id, &true);
+    client.verify_product(
+        &verifier,
+        &auction_id,
+        &true,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+
+    // The buyer pays for a shipping quote, then the seller ships
+    let shipper = setup_free_shipping(&env, &client, &admin);
+    env.mock_all_auths();
+    client.quote_shipping(
+        &auction_id,
+        &bidder2,
+        &String::from_str(&env, "LOCAL"),
+        &1,
+        &shipper,
+    );
 
-    // Add shipping info
     env.mock_all_auths();
     let tracking = String::from_str(&env, "TRK123456789");
     let carrier = String::from_str(&env, "Express Shipping");
     let est_delivery = auction.end_time + 86400; // 1 day
-    let shipping_cost = 500; // 5.00 units
     let recipient = String::from_str(&env, "123 Buyer St, City");
 
-    client.add_shipping_info(
-        &auction_id,
-        &tracking,
-        &carrier,
-        &est_delivery,
-        &shipping_cost,
-        &recipient,
-    );
+    client.add_shipping_info(&auction_id, &tracking, &carrier, &est_delivery, &recipient);
 
     // Update shipping status
     env.mock_all_auths();
-    client.update_shipping_status(&auction_id, &ShippingStatus::InTransit);
+    client.update_shipping_status(&auction_id, &seller, &ShippingStatus::InTransit);
 
     let auction = client.get_auction(&auction_id).unwrap();
     assert!(auction.has_shipping);
     assert_eq!(auction.shipping_status, ShippingStatus::InTransit);
 
-    // Mark as delivered
+    // Sellers cannot mark their own items delivered
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &seller, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+
+    // The buyer confirms delivery, which opens the dispute window
+    env.mock_all_auths();
+    client.confirm_delivery(&auction_id, &bidder2);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.shipping_status, ShippingStatus::Delivered);
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(
+        client.try_finalize_delivery(&auction_id),
+        Err(Ok(AuctionError::DisputeWindowOpen))
+    );
+
+    // Once the window passes without a dispute, anyone can complete the sale
+    env.ledger().set_timestamp(auction.dispute_deadline + 1);
+    client.finalize_delivery(&auction_id);
+
+    // Check auction is now completed and the seller was paid from escrow
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Completed);
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(balance(&env, &client, &seller), 1500);
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000);
+}
+
+// Test that carriers report delivery and shipping status only moves forward
+#[test]
+fn test_carrier_delivery_and_dispute_window() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    let carrier = Address::generate(&env);
+    env.mock_all_auths();
+    client.add_carrier(&admin, &carrier);
+    env.mock_all_auths();
+    client.set_dispute_window(&admin, &3600);
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &buyer, &1500, &1);
+    env.ledger().set_timestamp(auction.end_time + 1);
+    client.end_auction(&auction_id);
+
+    // Nothing ships before the buyer has paid for a quote
+    let text = String::from_str(&env, "TRK1");
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_add_shipping_info(&auction_id, &text, &text, &0, &text),
+        Err(Ok(AuctionError::NoShippingInfo))
+    );
+    let other_carrier = setup_free_shipping(&env, &client, &admin);
+    env.mock_all_auths();
+    client.quote_shipping(
+        &auction_id,
+        &buyer,
+        &String::from_str(&env, "LOCAL"),
+        &1,
+        &carrier,
+    );
+    env.mock_all_auths();
+    client.add_shipping_info(&auction_id, &text, &text, &0, &text);
+
+    // Shipping info cannot be added twice, and other callers cannot report progress
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_add_shipping_info(&auction_id, &text, &text, &0, &text),
+        Err(Ok(AuctionError::InvalidShippingTransition))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &buyer, &ShippingStatus::InTransit),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+
+    // Only the quoted carrier can report delivery
     env.mock_all_auths();
-    client.update_shipping_status(&auction_id, &ShippingStatus::Delivered);
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &other_carrier, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+
+    // The carrier reports delivery directly from Shipped
+    env.mock_all_auths();
+    client.update_shipping_status(&auction_id, &carrier, &ShippingStatus::Delivered);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.dispute_deadline, auction.delivered_at + 3600);
+
+    // Status cannot move backwards
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &carrier, &ShippingStatus::InTransit),
+        Err(Ok(AuctionError::InvalidShippingTransition))
+    );
+
+    // Disputes close with the window
+    env.ledger().set_timestamp(auction.dispute_deadline + 1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_open_dispute(&auction_id, &buyer, &text),
+        Err(Ok(AuctionError::DisputeWindowClosed))
+    );
+    client.finalize_delivery(&auction_id);
+    assert_eq!(balance(&env, &client, &seller), 1500);
+}
+
+// Test that shipping cannot be reported delivered once a sale is disputed or completed
+#[test]
+fn test_shipping_frozen_after_dispute() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    let carrier = setup_free_shipping(&env, &client, &admin);
+    let zone = String::from_str(&env, "LOCAL");
+    let text = String::from_str(&env, "TRK1");
+
+    // Sell and ship a single item and a lot
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let items = vec![
+        &env,
+        lot_item(&env, "Textbook", ProductCondition::LikeNew),
+        lot_item(&env, "Lab coat", ProductCondition::New),
+    ];
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.mock_all_auths();
+    let lot_id = client.create_lot_auction(
+        &seller,
+        &text,
+        &text,
+        &items,
+        &1000,
+        &auction.start_time,
+        &auction.end_time,
+        &default_options(),
+    );
+    env.ledger().set_timestamp(auction.start_time);
+    for id in [&auction_id, &lot_id] {
+        env.mock_all_auths();
+        client.start_auction(id);
+        env.mock_all_auths();
+        client.place_bid(id, &buyer, &1500, &1);
+    }
+    env.ledger().set_timestamp(auction.end_time + 1);
+    for id in [&auction_id, &lot_id] {
+        client.end_auction(id);
+        env.mock_all_auths();
+        client.quote_shipping(id, &buyer, &zone, &1, &carrier);
+        env.mock_all_auths();
+        client.add_shipping_info(id, &text, &text, &0, &text);
+        env.mock_all_auths();
+        client.open_dispute(id, &buyer, &text);
+    }
+
+    // A disputed sale takes no shipping updates
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &carrier, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_delivery(&auction_id, &buyer),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_lot_item_status(&lot_id, &carrier, &0, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_lot_item_delivery(&lot_id, &buyer, &1),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
 
-    // Check auction is now completed
+    // Nor does a sale completed by default judgment
+    let case = client.get_dispute(&auction_id);
+    env.ledger().set_timestamp(case.response_deadline + 1);
+    for id in [&auction_id, &lot_id] {
+        client.apply_default_judgment(id);
+    }
     let auction = client.get_auction(&auction_id).unwrap();
     assert_eq!(auction.status, crate::datatype::AuctionStatus::Completed);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &carrier, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_delivery(&auction_id, &buyer),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_lot_item_status(&lot_id, &carrier, &0, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_lot_item_delivery(&lot_id, &buyer, &1),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.shipping_status, ShippingStatus::Shipped);
+    assert_eq!(auction.dispute_deadline, 0);
+}
+
+// Test that the platform fee snapshotted at creation is deducted from the seller payout
+#[test]
+fn test_platform_fee() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    let treasury = Address::generate(&env);
+
+    // 2.5% plus a flat 10
+    let fee = FeeConfig {
+        fee_bps: 250,
+        flat_fee: 10,
+        recipient: treasury.clone(),
+    };
+    env.mock_all_auths();
+    client.set_fee_config(&admin, &fee);
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+
+    // Later changes do not affect auctions that are already running
+    env.mock_all_auths();
+    client.set_fee_config(
+        &admin,
+        &FeeConfig {
+            fee_bps: 5000,
+            flat_fee: 0,
+            recipient: admin.clone(),
+        },
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_fee_config(
+            &admin,
+            &FeeConfig {
+                fee_bps: 10_001,
+                flat_fee: 0,
+                recipient: admin.clone(),
+            }
+        ),
+        Err(Ok(AuctionError::InvalidFeeConfig))
+    );
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.fee.fee_bps, 250);
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &buyer, &1500, &1);
+    env.ledger().set_timestamp(auction.end_time + 1);
+    client.end_auction(&auction_id);
+
+    // Shipping costs 200 and is not charged a fee
+    let carrier = setup_free_shipping(&env, &client, &admin);
+    let zone = String::from_str(&env, "LOCAL");
+    env.mock_all_auths();
+    client.set_shipping_zone(&admin, &zone, &200);
+    env.mock_all_auths();
+    client.quote_shipping(&auction_id, &buyer, &zone, &1, &carrier);
+
+    let text = String::from_str(&env, "TRK1");
+    env.mock_all_auths();
+    client.add_shipping_info(&auction_id, &text, &text, &0, &text);
+    env.mock_all_auths();
+    client.confirm_delivery(&auction_id, &buyer);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.dispute_deadline + 1);
+    client.finalize_delivery(&auction_id);
+
+    // 1500 * 2.5% + 10 = 47 goes to the treasury, the rest and shipping to the seller
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.settlements.len(), 3);
+    let fee_settlement = auction.settlements.get(0).unwrap();
+    assert_eq!(fee_settlement.kind, SettlementKind::PlatformFee);
+    assert_eq!(fee_settlement.amount, 47);
+    let shipping_settlement = auction.settlements.get(1).unwrap();
+    assert_eq!(shipping_settlement.kind, SettlementKind::Shipping);
+    assert_eq!(shipping_settlement.amount, 200);
+    assert_eq!(balance(&env, &client, &treasury), 47);
+    assert_eq!(balance(&env, &client, &seller), 1453 + 200);
+    assert_eq!(client.get_total_fees(&payment_token(&env, &client)), 47);
+}
+
+// Test that the seller's share of a dispute ruling carries the platform fee
+#[test]
+fn test_platform_fee_on_dispute_payout() {
+    for (resolution, buyer_share) in [
+        (DisputeResolution::PaySeller, 0),
+        (DisputeResolution::Split(20), 300),
+    ] {
+        let (env, client, admin, seller, buyer) = setup_test();
+        let treasury = Address::generate(&env);
+        env.mock_all_auths();
+        client.set_fee_config(
+            &admin,
+            &FeeConfig {
+                fee_bps: 250,
+                flat_fee: 10,
+                recipient: treasury.clone(),
+            },
+        );
+
+        let auction_id = open_test_dispute(&env, &client, &seller, &buyer);
+        env.mock_all_auths();
+        client.submit_evidence(
+            &auction_id,
+            &seller,
+            &String::from_str(&env, "As described"),
+            &vec![&env],
+        );
+        env.mock_all_auths();
+        client.resolve_dispute(&admin, &auction_id, &resolution);
+        let case = client.get_dispute(&auction_id);
+        env.ledger().set_timestamp(case.appeal_deadline + 1);
+        client.finalize_dispute(&auction_id);
+
+        // 2.5% plus a flat 10 on the seller's share
+        let seller_share = 1500 - buyer_share;
+        let fee = seller_share * 250 / 10_000 + 10;
+        let auction = client.get_auction(&auction_id).unwrap();
+        assert_eq!(auction.escrowed_amount, 0);
+        assert_eq!(balance(&env, &client, &treasury), fee);
+        assert_eq!(balance(&env, &client, &seller), seller_share - fee);
+        assert_eq!(
+            balance(&env, &client, &buyer),
+            1_000_000 - 1500 + buyer_share
+        );
+        assert_eq!(client.get_total_fees(&payment_token(&env, &client)), fee);
+    }
+}
+
+// Test that bids are escrowed and outbid bidders are refunded
+#[test]
+fn test_bid_escrow_and_refund() {
+    let (env, client, _admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time + 10);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // First bid is pulled into the contract
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1200, &1);
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000 - 1200);
+    assert_eq!(balance(&env, &client, &client.address), 1200);
+
+    // Outbidding refunds the previous highest bidder
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder2, &1500, &1);
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000 - 1500);
+    assert_eq!(balance(&env, &client, &client.address), 1500);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.escrowed_amount, 1500);
+
+    // A bidder without enough funds cannot bid
+    let broke = Address::generate(&env);
+    env.mock_all_auths();
+    assert!(client
+        .try_place_bid(&auction_id, &broke, &2000, &1)
+        .is_err());
 }
 
 // Test dispute resolution
@@ -209,31 +716,2489 @@ fn test_dispute_resolution() {
     assert!(auction.has_dispute_reason);
     assert_eq!(auction.dispute_reason, reason);
 
-    // Resolver resolves in favor of buyer
+    // The resolver cannot rule before the seller has answered
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_resolve_dispute(&resolver, &auction_id, &DisputeResolution::RefundBuyer),
+        Err(Ok(AuctionError::SellerResponsePending))
+    );
+
+    // Seller responds, then the resolver rules in favor of the buyer
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &seller,
+        &String::from_str(&env, "Shipped as listed"),
+        &vec![&env],
+    );
     env.mock_all_auths();
-    client.resolve_dispute(&resolver, &auction_id, &DisputeStatus::ResolvedForBuyer);
+    client.resolve_dispute(&resolver, &auction_id, &DisputeResolution::RefundBuyer);
+
+    // Funds stay in escrow until the appeal window has passed
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.dispute_status, DisputeStatus::Ruled);
+    assert_eq!(auction.escrowed_amount, 1500);
+    assert_eq!(
+        client.try_finalize_dispute(&auction_id),
+        Err(Ok(AuctionError::DisputeWindowOpen))
+    );
+
+    let case = client.get_dispute(&auction_id);
+    env.ledger().set_timestamp(case.appeal_deadline + 1);
+    client.finalize_dispute(&auction_id);
 
-    // Check final status
+    // Check final status and that the buyer got the escrow back
     let auction = client.get_auction(&auction_id).unwrap();
     assert_eq!(auction.dispute_status, DisputeStatus::ResolvedForBuyer);
     assert_eq!(auction.status, crate::datatype::AuctionStatus::Completed);
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(auction.settlements.len(), 1);
+    let settlement = auction.settlements.get(0).unwrap();
+    assert_eq!(settlement.recipient, buyer);
+    assert_eq!(settlement.amount, 1500);
+    assert_eq!(settlement.kind, SettlementKind::DisputeRefund);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000);
+    assert_eq!(balance(&env, &client, &seller), 0);
 }
 
+// Test a dispute resolved with a percentage split
 #[test]
-fn test_shipping_cost_calculation() {
-    let (env, client, _admin, seller, _bidder) = setup_test();
+fn test_dispute_split_resolution() {
+    let (env, client, admin, seller, buyer) = setup_test();
 
-    // Create auction
     let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time + 10);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &buyer, &2000, &1);
+    env.ledger().set_timestamp(auction.end_time + 10);
+    client.end_auction(&auction_id);
+
+    env.mock_all_auths();
+    client.open_dispute(&auction_id, &buyer, &String::from_str(&env, "Damaged"));
+
+    // Percentages above 100 are rejected
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_resolve_dispute(&admin, &auction_id, &DisputeResolution::Split(101)),
+        Err(Ok(AuctionError::InvalidSplit))
+    );
+
+    // Admin refunds 25% to the buyer, the rest goes to the seller
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &seller,
+        &String::from_str(&env, "Packed carefully"),
+        &vec![&env],
+    );
+    env.mock_all_auths();
+    client.resolve_dispute(&admin, &auction_id, &DisputeResolution::Split(25));
+    let case = client.get_dispute(&auction_id);
+    env.ledger().set_timestamp(case.appeal_deadline + 1);
+    client.finalize_dispute(&auction_id);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.dispute_status, DisputeStatus::ResolvedSplit);
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(auction.settlements.len(), 2);
+    assert_eq!(auction.settlements.get(0).unwrap().amount, 500);
+    assert_eq!(auction.settlements.get(1).unwrap().recipient, seller);
+    assert_eq!(
+        auction.settlements.get(1).unwrap().kind,
+        SettlementKind::DisputePayout
+    );
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000 - 1500);
+    assert_eq!(balance(&env, &client, &seller), 1500);
+}
+
+// Helper function to run an auction to a 1500 sale and open a dispute on it
+fn open_test_dispute(
+    env: &Env,
+    client: &AuctionContractClient<'_>,
+    seller: &Address,
+    buyer: &Address,
+) -> BytesN<32> {
+    let auction_id = create_test_auction(env, client, seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time + 10);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, buyer, &1500, &1);
+    env.ledger().set_timestamp(auction.end_time + 10);
+    client.end_auction(&auction_id);
+
+    env.mock_all_auths();
+    client.open_dispute(
+        &auction_id,
+        buyer,
+        &String::from_str(env, "Not as described"),
+    );
+    auction_id
+}
 
-    // Calculate shipping for different speeds
-    let destination = String::from_str(&env, "123 Ship St, Cityville");
+// Test the dispute case file, appeal to a second resolver and its final ruling
+#[test]
+fn test_dispute_evidence_and_appeal() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    let first_resolver = Address::generate(&env);
+    let second_resolver = Address::generate(&env);
+    env.mock_all_auths();
+    client.add_resolver(&admin, &first_resolver);
+    env.mock_all_auths();
+    client.add_resolver(&admin, &second_resolver);
+    env.mock_all_auths();
+    client.set_dispute_deadlines(&admin, &1000, &500);
+
+    let auction_id = open_test_dispute(&env, &client, &seller, &buyer);
+    let opened_at = env.ledger().timestamp();
+
+    // Both parties file evidence; outsiders cannot
+    let photo = BytesN::from_array(&env, &[7; 32]);
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &buyer,
+        &String::from_str(&env, "Screen is cracked"),
+        &vec![&env, photo.clone()],
+    );
+    let stranger = Address::generate(&env);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_submit_evidence(
+            &auction_id,
+            &stranger,
+            &String::from_str(&env, "Spam"),
+            &vec![&env]
+        ),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    env.ledger().set_timestamp(opened_at + 100);
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &seller,
+        &String::from_str(&env, "Photos before shipping"),
+        &vec![&env],
+    );
 
-    let express_cost = client.calculate_shipping_cost(&auction_id, &destination, &1);
-    let standard_cost = client.calculate_shipping_cost(&auction_id, &destination, &2);
-    let economy_cost = client.calculate_shipping_cost(&auction_id, &destination, &3);
+    let case = client.get_dispute(&auction_id);
+    assert_eq!(case.buyer, buyer);
+    assert_eq!(case.seller, seller);
+    assert_eq!(case.response_deadline, opened_at + 1000);
+    assert!(case.seller_responded);
+    assert_eq!(case.evidence.len(), 2);
+    let evidence = case.evidence.get(0).unwrap();
+    assert_eq!(evidence.submitter, buyer);
+    assert_eq!(evidence.content_hashes, vec![&env, photo]);
+    assert_eq!(case.evidence.get(1).unwrap().timestamp, opened_at + 100);
 
-    // Express should be more expensive than standard, which should be more than economy
-    assert!(express_cost > standard_cost);
-    assert!(standard_cost > economy_cost);
+    // The first resolver sides with the seller; the buyer appeals in time
+    env.mock_all_auths();
+    client.resolve_dispute(&first_resolver, &auction_id, &DisputeResolution::PaySeller);
+    let case = client.get_dispute(&auction_id);
+    assert_eq!(case.appeal_deadline, opened_at + 100 + 500);
+    env.mock_all_auths();
+    client.appeal_dispute(&auction_id, &buyer);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().dispute_status,
+        DisputeStatus::Appealed
+    );
+
+    // A ruling cannot be appealed twice, nor finalized once appealed
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_appeal_dispute(&auction_id, &seller),
+        Err(Ok(AuctionError::NoPendingRuling))
+    );
+    assert_eq!(
+        client.try_finalize_dispute(&auction_id),
+        Err(Ok(AuctionError::NoPendingRuling))
+    );
+
+    // The appeal must go to a different resolver, whose ruling settles at once
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_resolve_dispute(&first_resolver, &auction_id, &DisputeResolution::PaySeller),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    env.mock_all_auths();
+    client.resolve_dispute(
+        &second_resolver,
+        &auction_id,
+        &DisputeResolution::RefundBuyer,
+    );
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.dispute_status, DisputeStatus::ResolvedForBuyer);
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Completed);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000);
+
+    let case = client.get_dispute(&auction_id);
+    assert_eq!(case.rulings.len(), 2);
+    assert_eq!(case.rulings.get(0).unwrap().resolver, first_resolver);
+    assert_eq!(
+        case.rulings.get(1).unwrap().resolution,
+        DisputeResolution::RefundBuyer
+    );
+}
+
+// Test that a buyer filling their evidence quota cannot block the seller's response
+#[test]
+fn test_evidence_limit_per_party() {
+    let (env, client, _admin, seller, buyer) = setup_test();
+    let auction_id = open_test_dispute(&env, &client, &seller, &buyer);
+
+    for _ in 0..10 {
+        env.mock_all_auths();
+        client.submit_evidence(
+            &auction_id,
+            &buyer,
+            &String::from_str(&env, "More photos"),
+            &vec![&env],
+        );
+    }
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_submit_evidence(
+            &auction_id,
+            &buyer,
+            &String::from_str(&env, "Even more"),
+            &vec![&env]
+        ),
+        Err(Ok(AuctionError::EvidenceLimitReached))
+    );
+
+    // The seller still has their own quota and their reply counts as a response
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &seller,
+        &String::from_str(&env, "Item shipped as described"),
+        &vec![&env],
+    );
+    let case = client.get_dispute(&auction_id);
+    assert!(case.seller_responded);
+    assert_eq!(case.evidence.len(), 11);
+    env.ledger().set_timestamp(case.response_deadline + 1);
+    assert_eq!(
+        client.try_apply_default_judgment(&auction_id),
+        Err(Ok(AuctionError::DisputeNotAllowed))
+    );
+}
+
+// Test that a seller who never responds loses by default and appeals expire
+#[test]
+fn test_dispute_default_judgment() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    env.mock_all_auths();
+    client.set_dispute_deadlines(&admin, &1000, &500);
+
+    let auction_id = open_test_dispute(&env, &client, &seller, &buyer);
+    let case = client.get_dispute(&auction_id);
+
+    // Not available while the seller can still respond
+    assert_eq!(
+        client.try_apply_default_judgment(&auction_id),
+        Err(Ok(AuctionError::SellerResponsePending))
+    );
+
+    // A late response is kept on file but does not count
+    env.ledger().set_timestamp(case.response_deadline + 1);
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &seller,
+        &String::from_str(&env, "Sorry, was away"),
+        &vec![&env],
+    );
+    let case = client.get_dispute(&auction_id);
+    assert!(!case.seller_responded);
+    assert_eq!(case.evidence.len(), 1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_resolve_dispute(&admin, &auction_id, &DisputeResolution::PaySeller),
+        Err(Ok(AuctionError::SellerResponsePending))
+    );
+
+    client.apply_default_judgment(&auction_id);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.dispute_status, DisputeStatus::ResolvedForBuyer);
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000);
+
+    // Appeals are only possible before the appeal deadline
+    let (env, client, admin, seller, buyer) = setup_test();
+    let auction_id = open_test_dispute(&env, &client, &seller, &buyer);
+    env.mock_all_auths();
+    client.submit_evidence(
+        &auction_id,
+        &seller,
+        &String::from_str(&env, "Tracking shows delivered"),
+        &vec![&env],
+    );
+    env.mock_all_auths();
+    client.resolve_dispute(&admin, &auction_id, &DisputeResolution::PaySeller);
+    let case = client.get_dispute(&auction_id);
+    env.ledger().set_timestamp(case.appeal_deadline + 1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_appeal_dispute(&auction_id, &buyer),
+        Err(Ok(AuctionError::DisputeWindowClosed))
+    );
+    client.finalize_dispute(&auction_id);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().dispute_status,
+        DisputeStatus::ResolvedForSeller
+    );
+    assert_eq!(balance(&env, &client, &seller), 1500);
+}
+
+// Test shipping costs from the admin rate table
+#[test]
+fn test_shipping_cost_calculation() {
+    let (env, client, admin, seller, bidder) = setup_test();
+    let zone = String::from_str(&env, "EU-1");
+
+    // Zone base 400; express doubles, economy is 80%; 50 per unit from 2 units,
+    // 10% off from 2 units
+    env.mock_all_auths();
+    client.set_shipping_zone(&admin, &zone, &400);
+    let rates = ShippingRates {
+        speed_multipliers: map![&env, (1, 20_000), (2, 10_000), (3, 8_000)],
+        quantity_tiers: vec![
+            &env,
+            QuantityTier {
+                min_quantity: 2,
+                unit_cost: 50,
+            },
+        ],
+        bulk_discounts: vec![
+            &env,
+            BulkDiscount {
+                min_quantity: 2,
+                discount_bps: 1_000,
+            },
+        ],
+    };
+    env.mock_all_auths();
+    client.set_shipping_rates(&admin, &rates);
+
+    // Before there is a winner, costs are for one unit
+    let auction_id = create_test_auction(&env, &client, &seller);
+    assert_eq!(client.calculate_shipping_cost(&auction_id, &zone, &1), 800);
+    assert_eq!(client.calculate_shipping_cost(&auction_id, &zone, &2), 400);
+    assert_eq!(client.calculate_shipping_cost(&auction_id, &zone, &3), 320);
+
+    // Unknown zones and speeds have no rate
+    assert_eq!(
+        client.try_calculate_shipping_cost(&auction_id, &String::from_str(&env, "MARS"), &1),
+        Err(Ok(AuctionError::NoShippingInfo))
+    );
+    assert_eq!(
+        client.try_calculate_shipping_cost(&auction_id, &zone, &9),
+        Err(Ok(AuctionError::NoShippingInfo))
+    );
+
+    // Two units: (400 + 2 * 50) at standard speed, less 10%
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder, &1500, &2);
+    assert_eq!(client.calculate_shipping_cost(&auction_id, &zone, &2), 450);
+
+    // Rate tables are validated
+    let mut unsorted = rates.clone();
+    unsorted.bulk_discounts.push_back(BulkDiscount {
+        min_quantity: 2,
+        discount_bps: 2_000,
+    });
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_shipping_rates(&admin, &unsorted),
+        Err(Ok(AuctionError::InvalidConfig))
+    );
+    let mut zero_speed = rates.clone();
+    zero_speed.speed_multipliers.set(4, 0);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_shipping_rates(&admin, &zero_speed),
+        Err(Ok(AuctionError::InvalidConfig))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_shipping_zone(&admin, &zone, &-1),
+        Err(Ok(AuctionError::InvalidConfig))
+    );
+    env.mock_all_auths();
+    client.remove_shipping_zone(&admin, &zone);
+    assert_eq!(client.get_shipping_zone(&zone), None);
+}
+
+// Test that the buyer pays exactly the quoted shipping with a seller-approved carrier
+#[test]
+fn test_shipping_quote() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    let carrier = setup_free_shipping(&env, &client, &admin);
+    let preferred = setup_free_shipping(&env, &client, &admin);
+    let zone = String::from_str(&env, "LOCAL");
+    env.mock_all_auths();
+    client.set_shipping_zone(&admin, &zone, &300);
+
+    // The seller only ships with their preferred carrier
+    let stranger = Address::generate(&env);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_seller_carriers(&seller, &vec![&env, stranger]),
+        Err(Ok(AuctionError::RoleNotAssigned))
+    );
+    env.mock_all_auths();
+    client.set_seller_carriers(&seller, &vec![&env, preferred.clone()]);
+    assert_eq!(
+        client.get_seller_carriers(&seller),
+        vec![&env, preferred.clone()]
+    );
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &buyer, &1500, &1);
+
+    // No quotes before the auction has ended
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_quote_shipping(&auction_id, &buyer, &zone, &1, &preferred),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.ledger().set_timestamp(auction.end_time + 1);
+    client.end_auction(&auction_id);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_quote_shipping(&auction_id, &seller, &zone, &1, &preferred),
+        Err(Ok(AuctionError::NotHighestBidder))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_quote_shipping(&auction_id, &buyer, &zone, &1, &carrier),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+
+    // The quoted cost is escrowed and stored on the auction
+    env.mock_all_auths();
+    assert_eq!(
+        client.quote_shipping(&auction_id, &buyer, &zone, &1, &preferred),
+        300
+    );
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert!(auction.has_shipping_quote);
+    assert_eq!(auction.shipping_quote.cost, 300);
+    assert_eq!(auction.shipping_quote.carrier, preferred);
+    assert_eq!(auction.escrowed_amount, 1800);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000 - 1800);
+
+    // Later rate changes do not alter the quote; requoting refunds the old one
+    env.mock_all_auths();
+    client.set_shipping_zone(&admin, &zone, &250);
+    env.mock_all_auths();
+    client.quote_shipping(&auction_id, &buyer, &zone, &1, &preferred);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000 - 1750);
+    env.mock_all_auths();
+    client.set_shipping_zone(&admin, &zone, &900);
+
+    let text = String::from_str(&env, "TRK1");
+    env.mock_all_auths();
+    client.add_shipping_info(&auction_id, &text, &text, &0, &text);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.shipping_cost, 250);
+
+    // No requotes once shipped
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_quote_shipping(&auction_id, &buyer, &zone, &1, &preferred),
+        Err(Ok(AuctionError::InvalidShippingTransition))
+    );
+
+    env.mock_all_auths();
+    client.update_shipping_status(&auction_id, &preferred, &ShippingStatus::Delivered);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.dispute_deadline + 1);
+    client.finalize_delivery(&auction_id);
+    assert_eq!(balance(&env, &client, &seller), 1750);
+}
+
+// Test anti-sniping soft close
+#[test]
+fn test_soft_close_extension() {
+    let (env, client, _admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+
+    // Bids in the last 5 minutes add 10 minutes, at most 15 minutes in total
+    let mut options = default_options();
+    options.extension_window = 300;
+    options.extension_duration = 600;
+    options.max_extension = 900;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    let original_end = auction.end_time;
+
+    env.ledger().set_timestamp(auction.start_time + 10);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // A bid well before the window does not extend
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1200, &1);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().end_time,
+        original_end
+    );
+
+    // A late bid extends the deadline
+    env.ledger().set_timestamp(original_end - 60);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder2, &1300, &1);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().end_time,
+        original_end + 600
+    );
+
+    // The original deadline no longer ends the auction
+    env.ledger().set_timestamp(original_end + 1);
+    assert_eq!(
+        client.try_end_auction(&auction_id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+
+    // Another late bid is capped by the maximum extension
+    env.ledger().set_timestamp(original_end + 590);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1400, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.end_time, original_end + 900);
+    assert_eq!(auction.max_end_time, original_end + 900);
+
+    env.ledger().set_timestamp(original_end + 900);
+    client.end_auction(&auction_id);
+}
+
+// Test that inconsistent or overflowing soft-close settings are rejected and
+// large windows cannot overflow while bidding
+#[test]
+fn test_soft_close_validation() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+    let name = String::from_str(&env, "Test Item");
+    let start_time = env.ledger().timestamp() + 100;
+    let try_create = |options: &AuctionOptions| {
+        env.mock_all_auths();
+        client.try_create_auction(
+            &seller,
+            &name,
+            &name,
+            &ProductCondition::Good,
+            &vec![&env],
+            &1,
+            &1000,
+            &start_time,
+            &(start_time + 3600),
+            options,
+        )
+    };
+
+    // A window needs room to extend into
+    let mut options = default_options();
+    options.extension_window = 300;
+    options.extension_duration = 600;
+    assert_eq!(
+        try_create(&options),
+        Err(Ok(AuctionError::InvalidSoftClose))
+    );
+
+    // The latest end time must fit in a timestamp
+    options.max_extension = u64::MAX;
+    assert_eq!(
+        try_create(&options),
+        Err(Ok(AuctionError::InvalidSoftClose))
+    );
+
+    // Huge windows and durations extend up to the cap without overflowing
+    options.extension_window = u64::MAX;
+    options.extension_duration = u64::MAX;
+    options.max_extension = 900;
+    let auction_id = try_create(&options).unwrap().unwrap();
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder, &1000, &1);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().end_time,
+        auction.end_time + 900
+    );
+}
+
+// Test Dutch (descending-price) auctions
+#[test]
+fn test_dutch_auction() {
+    let (env, client, _admin, seller, buyer) = setup_test();
+    let other = Address::generate(&env);
+    fund(&env, &client, &other, 1_000_000);
+
+    // Starts at 5000 per unit, drops 500 every 10 minutes, never below 2000
+    let mut options = default_options();
+    options.auction_type = AuctionType::Dutch(DutchSchedule {
+        start_price: 5000,
+        floor_price: 2000,
+        price_decrement: 500,
+        decrement_interval: 600,
+    });
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    assert_eq!(client.get_current_price(&auction_id), 5000);
+
+    // English-style bids are rejected
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &other, &6000, &1),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // Price decays over time down to the floor
+    env.ledger().set_timestamp(auction.start_time + 1300);
+    assert_eq!(client.get_current_price(&auction_id), 4000);
+    env.ledger().set_timestamp(auction.start_time + 3500);
+    assert_eq!(client.get_current_price(&auction_id), 2500);
+
+    // First buyer to accept wins both units at the current price
+    env.mock_all_auths();
+    client.accept_price(&auction_id, &buyer, &2);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(auction.highest_bidder, buyer);
+    assert_eq!(auction.highest_bid_amount, 5000);
+    assert_eq!(auction.escrowed_amount, 5000);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000 - 5000);
+
+    // Nobody else can buy once it has ended
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_accept_price(&auction_id, &other, &1),
+        Err(Ok(AuctionError::AuctionNotActive))
+    );
+}
+
+// Test Dutch schedule validation
+#[test]
+fn test_dutch_auction_floor_below_reserve() {
+    let (env, client, _admin, seller, _bidder) = setup_test();
+
+    let mut options = default_options();
+    options.auction_type = AuctionType::Dutch(DutchSchedule {
+        start_price: 5000,
+        floor_price: 500, // reserve price in the helper is 1000
+        price_decrement: 500,
+        decrement_interval: 600,
+    });
+
+    let name = String::from_str(&env, "Test Item");
+    env.mock_all_auths();
+    let result = client.try_create_auction(
+        &seller,
+        &name,
+        &name,
+        &ProductCondition::New,
+        &vec![&env],
+        &1,
+        &1000,
+        &100,
+        &1000,
+        &options,
+    );
+    assert_eq!(result, Err(Ok(AuctionError::InvalidDutchSchedule)));
+}
+
+// Helper function to build a sealed bid commitment
+fn commitment(env: &Env, amount: i128, salt: &Bytes) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, &amount.to_be_bytes());
+    preimage.append(salt);
+    env.crypto().sha256(&preimage).into()
+}
+
+// Test sealed-bid second-price auctions
+#[test]
+fn test_sealed_bid_auction() {
+    let (env, client, _admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    let bidder3 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+    fund(&env, &client, &bidder3, 1_000_000);
+
+    let mut options = default_options();
+    options.auction_type = AuctionType::Sealed(SealedBidConfig {
+        reveal_duration: 600,
+        deposit: 100,
+    });
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // Open bids are not accepted
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder1, &1500, &1),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // Everyone commits; only the deposit is escrowed
+    let salt1 = Bytes::from_slice(&env, b"salt-one");
+    let salt2 = Bytes::from_slice(&env, b"salt-two");
+    let salt3 = Bytes::from_slice(&env, b"salt-three");
+    env.mock_all_auths();
+    client.commit_bid(&auction_id, &bidder1, &commitment(&env, 3000, &salt1));
+    client.commit_bid(&auction_id, &bidder2, &commitment(&env, 2000, &salt2));
+    client.commit_bid(&auction_id, &bidder3, &commitment(&env, 2500, &salt3));
+    assert_eq!(balance(&env, &client, &client.address), 300);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_commit_bid(&auction_id, &bidder1, &commitment(&env, 3500, &salt1)),
+        Err(Ok(AuctionError::AlreadyCommitted))
+    );
+
+    // Reveals are rejected until the bidding phase ends
+    env.ledger().set_timestamp(auction.end_time);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_reveal_bid(&auction_id, &bidder1, &3000, &salt1),
+        Err(Ok(AuctionError::NotRevealing))
+    );
+
+    // A reveal that does not match the commitment is rejected
+    env.ledger().set_timestamp(auction.end_time + 1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_reveal_bid(&auction_id, &bidder1, &2999, &salt1),
+        Err(Ok(AuctionError::CommitmentMismatch))
+    );
+
+    // Reveals are accepted before anyone moves the auction to Revealing;
+    // a bid behind the leader is refunded at once
+    env.mock_all_auths();
+    client.reveal_bid(&auction_id, &bidder2, &2000, &salt2);
+    client.reveal_bid(&auction_id, &bidder1, &3000, &salt1);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000);
+    assert_eq!(balance(&env, &client, &client.address), 3100);
+
+    env.mock_all_auths();
+    client.end_auction(&auction_id);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().status,
+        crate::datatype::AuctionStatus::Revealing
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_reveal_bid(&auction_id, &bidder2, &2000, &salt2),
+        Err(Ok(AuctionError::AlreadyRevealed))
+    );
+
+    // Bidder 3 never reveals
+
+    // The reveal phase must elapse before the auction can end
+    assert_eq!(
+        client.try_end_auction(&auction_id),
+        Err(Ok(AuctionError::RevealPhaseNotEnded))
+    );
+    env.ledger().set_timestamp(auction.end_time + 601);
+    client.end_auction(&auction_id);
+
+    // Highest bidder wins at the second-highest revealed price
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(auction.highest_bidder, bidder1);
+    assert_eq!(auction.highest_bid_amount, 2000);
+    assert_eq!(auction.escrowed_amount, 2000);
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000 - 2000);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000);
+
+    // The unrevealed deposit is forfeited to the seller
+    assert_eq!(balance(&env, &client, &bidder3), 1_000_000 - 100);
+    assert_eq!(balance(&env, &client, &seller), 100);
+    assert_eq!(
+        auction.settlements.get(0).unwrap().kind,
+        SettlementKind::ForfeitedDeposit
+    );
+}
+
+// Test that sealed bids revealed before anyone ends the auction are settled
+// in one call once the reveal phase is over
+#[test]
+fn test_sealed_bid_reveal_without_reveal_phase_call() {
+    let (env, client, _admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+
+    let mut options = default_options();
+    options.auction_type = AuctionType::Sealed(SealedBidConfig {
+        reveal_duration: 600,
+        deposit: 100,
+    });
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // Equal bids; the later commitment is revealed first
+    let salt1 = Bytes::from_slice(&env, b"salt-one");
+    let salt2 = Bytes::from_slice(&env, b"salt-two");
+    env.mock_all_auths();
+    client.commit_bid(&auction_id, &bidder1, &commitment(&env, 2000, &salt1));
+    client.commit_bid(&auction_id, &bidder2, &commitment(&env, 2000, &salt2));
+
+    env.ledger().set_timestamp(auction.end_time + 600);
+    env.mock_all_auths();
+    client.reveal_bid(&auction_id, &bidder2, &2000, &salt2);
+    client.reveal_bid(&auction_id, &bidder1, &2000, &salt1);
+
+    // The earlier commitment wins the tie
+    env.ledger().set_timestamp(auction.end_time + 601);
+    env.mock_all_auths();
+    client.end_auction(&auction_id);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(auction.highest_bidder, bidder1);
+    assert_eq!(auction.highest_bid_amount, 2000);
+    assert_eq!(auction.escrowed_amount, 2000);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000);
+    assert_eq!(auction.settlements.len(), 0);
+}
+
+// Test multi-unit uniform-price clearing
+#[test]
+fn test_multi_unit_uniform_price_clearing() {
+    let (env, client, _admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    let bidder3 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+    fund(&env, &client, &bidder3, 1_000_000);
+
+    // 5 copies, reserve of 1000 per copy
+    let mut options = default_options();
+    options.auction_type = AuctionType::MultiUnit;
+    let name = String::from_str(&env, "Textbook");
+    let start_time = env.ledger().timestamp() + 100;
+    env.mock_all_auths();
+    let auction_id = client.create_auction(
+        &seller,
+        &name,
+        &name,
+        &ProductCondition::New,
+        &vec![&env],
+        &5,
+        &1000,
+        &start_time,
+        &(start_time + 3600),
+        &options,
+    );
+    env.ledger().set_timestamp(start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // Bids below the reserve unit price are rejected
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder1, &900, &1),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+
+    // Demand of 7 units for 5 in stock; every bid is escrowed in full
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1500, &2);
+    client.place_bid(&auction_id, &bidder2, &1200, &2);
+    client.place_bid(&auction_id, &bidder3, &1300, &2);
+    client.place_bid(&auction_id, &bidder2, &1100, &1);
+    assert_eq!(
+        balance(&env, &client, &client.address),
+        3000 + 2400 + 2600 + 1100
+    );
+
+    env.ledger().set_timestamp(start_time + 3600);
+    client.end_auction(&auction_id);
+
+    // 1500x2 and 1300x2 fill fully, 1200x2 gets one unit and sets the price
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.clearing_price, 1200);
+    assert_eq!(auction.escrowed_amount, 5 * 1200);
+
+    let allocations = client.get_allocations(&auction_id);
+    assert_eq!(allocations.len(), 3);
+    let first = allocations.get(0).unwrap();
+    assert_eq!((first.bidder, first.quantity), (bidder1.clone(), 2));
+    let second = allocations.get(1).unwrap();
+    assert_eq!((second.bidder, second.quantity), (bidder2.clone(), 1));
+    let third = allocations.get(2).unwrap();
+    assert_eq!((third.bidder, third.quantity), (bidder3.clone(), 2));
+
+    // Everyone pays the clearing price and unfilled bids are refunded
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000 - 2400);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000 - 1200);
+    assert_eq!(balance(&env, &client, &bidder3), 1_000_000 - 2400);
+}
+
+// Test that each multi-unit winner's payment is delivered, disputed and paid out separately
+#[test]
+fn test_multi_unit_allocation_settlement() {
+    let (env, client, admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+
+    let mut options = default_options();
+    options.auction_type = AuctionType::MultiUnit;
+    let name = String::from_str(&env, "Textbook");
+    let start_time = env.ledger().timestamp() + 100;
+    env.mock_all_auths();
+    let auction_id = client.create_auction(
+        &seller,
+        &name,
+        &name,
+        &ProductCondition::New,
+        &vec![&env],
+        &4,
+        &1000,
+        &start_time,
+        &(start_time + 3600),
+        &options,
+    );
+    env.ledger().set_timestamp(start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1500, &2);
+    client.place_bid(&auction_id, &bidder2, &1200, &2);
+    env.ledger().set_timestamp(start_time + 3600);
+    client.end_auction(&auction_id);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().escrowed_amount,
+        4800
+    );
+
+    // The single-buyer shipping and dispute flow does not apply
+    let carrier = setup_free_shipping(&env, &client, &admin);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_quote_shipping(
+            &auction_id,
+            &bidder1,
+            &String::from_str(&env, "LOCAL"),
+            &1,
+            &carrier
+        ),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+    assert_eq!(
+        client.try_open_dispute(&auction_id, &bidder1, &name),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // bidder1 disputes and, once the ruling stands, is refunded only their own 2400
+    env.mock_all_auths();
+    client.open_allocation_dispute(&auction_id, &bidder1, &name);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_resolve_allocation_dispute(
+            &admin,
+            &auction_id,
+            &bidder1,
+            &DisputeResolution::RefundBuyer,
+        ),
+        Err(Ok(AuctionError::SellerResponsePending))
+    );
+    env.mock_all_auths();
+    client.submit_allocation_evidence(&auction_id, &bidder1, &seller, &name, &vec![&env]);
+    env.mock_all_auths();
+    client.resolve_allocation_dispute(
+        &admin,
+        &auction_id,
+        &bidder1,
+        &DisputeResolution::RefundBuyer,
+    );
+    let case = client.get_allocation_dispute(&auction_id, &bidder1);
+    env.ledger().set_timestamp(case.appeal_deadline + 1);
+    client.finalize_allocation_dispute(&auction_id, &bidder1);
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.escrowed_amount, 2400);
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+
+    // bidder2 receives their units; the seller is paid after the dispute window
+    let stranger = Address::generate(&env);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_allocation_delivery(&auction_id, &stranger),
+        Err(Ok(AuctionError::NotHighestBidder))
+    );
+    env.mock_all_auths();
+    client.confirm_allocation_delivery(&auction_id, &bidder2);
+    assert_eq!(
+        client.try_finalize_allocation(&auction_id, &bidder2),
+        Err(Ok(AuctionError::DisputeWindowOpen))
+    );
+    let allocation = client.get_allocations(&auction_id).get(1).unwrap();
+    env.ledger().set_timestamp(allocation.dispute_deadline + 1);
+    client.finalize_allocation(&auction_id, &bidder2);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Completed);
+    assert_eq!(balance(&env, &client, &seller), 2400);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000 - 2400);
+}
+
+// Test that allocation disputes go through the case file flow and that the
+// flat fee is charged on every allocation paid out to the seller
+#[test]
+fn test_allocation_dispute_case_and_flat_fee() {
+    let (env, client, admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    let bidder3 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+    fund(&env, &client, &bidder3, 1_000_000);
+    let resolver = Address::generate(&env);
+    env.mock_all_auths();
+    client.add_resolver(&admin, &resolver);
+    let treasury = Address::generate(&env);
+    env.mock_all_auths();
+    client.set_fee_config(
+        &admin,
+        &FeeConfig {
+            fee_bps: 0,
+            flat_fee: 10,
+            recipient: treasury.clone(),
+        },
+    );
+
+    // Three winners of two units each at a uniform 1200
+    let mut options = default_options();
+    options.auction_type = AuctionType::MultiUnit;
+    let name = String::from_str(&env, "Textbook");
+    let start_time = env.ledger().timestamp() + 100;
+    env.mock_all_auths();
+    let auction_id = client.create_auction(
+        &seller,
+        &name,
+        &name,
+        &ProductCondition::New,
+        &vec![&env],
+        &6,
+        &1000,
+        &start_time,
+        &(start_time + 3600),
+        &options,
+    );
+    env.ledger().set_timestamp(start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1500, &2);
+    client.place_bid(&auction_id, &bidder2, &1300, &2);
+    client.place_bid(&auction_id, &bidder3, &1200, &2);
+    env.ledger().set_timestamp(start_time + 3600);
+    client.end_auction(&auction_id);
+
+    // Each winner has their own case file; only the winner opens it
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_open_allocation_dispute(&auction_id, &seller, &name),
+        Err(Ok(AuctionError::NotHighestBidder))
+    );
+    env.mock_all_auths();
+    client.open_allocation_dispute(&auction_id, &bidder1, &name);
+    env.mock_all_auths();
+    client.open_allocation_dispute(&auction_id, &bidder2, &name);
+    let case = client.get_allocation_dispute(&auction_id, &bidder2);
+    assert_eq!(case.buyer, bidder2);
+    assert_eq!(case.seller, seller);
+    assert_eq!(
+        client
+            .try_get_allocation_dispute(&auction_id, &bidder3)
+            .err(),
+        Some(Ok(AuctionError::NoOpenDispute))
+    );
+    assert_eq!(
+        client.try_get_dispute(&auction_id).err(),
+        Some(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // The seller answers bidder2 but not bidder1, who wins by default
+    env.mock_all_auths();
+    client.submit_allocation_evidence(&auction_id, &bidder2, &seller, &name, &vec![&env]);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_submit_allocation_evidence(&auction_id, &bidder2, &bidder3, &name, &vec![&env]),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_allocation_default_judgment(&auction_id, &bidder1),
+        Err(Ok(AuctionError::SellerResponsePending))
+    );
+    env.ledger().set_timestamp(case.response_deadline + 1);
+    client.allocation_default_judgment(&auction_id, &bidder1);
+    assert_eq!(balance(&env, &client, &bidder1), 1_000_000);
+    assert_eq!(
+        client.try_allocation_default_judgment(&auction_id, &bidder2),
+        Err(Ok(AuctionError::DisputeNotAllowed))
+    );
+
+    // bidder2 appeals the first ruling; a different resolver settles it
+    env.mock_all_auths();
+    client.resolve_allocation_dispute(&admin, &auction_id, &bidder2, &DisputeResolution::PaySeller);
+    env.mock_all_auths();
+    client.appeal_allocation_dispute(&auction_id, &bidder2, &bidder2);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_resolve_allocation_dispute(
+            &admin,
+            &auction_id,
+            &bidder2,
+            &DisputeResolution::PaySeller,
+        ),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    env.mock_all_auths();
+    client.resolve_allocation_dispute(
+        &resolver,
+        &auction_id,
+        &bidder2,
+        &DisputeResolution::Split(50),
+    );
+    assert_eq!(
+        client
+            .get_allocation_dispute(&auction_id, &bidder2)
+            .rulings
+            .len(),
+        2
+    );
+    let allocation = client.get_allocations(&auction_id).get(1).unwrap();
+    assert_eq!(allocation.dispute_status, DisputeStatus::ResolvedSplit);
+    assert!(allocation.settled);
+    assert_eq!(balance(&env, &client, &bidder2), 1_000_000 - 1200);
+
+    // bidder3 receives their units without a dispute
+    env.mock_all_auths();
+    client.confirm_allocation_delivery(&auction_id, &bidder3);
+    let allocation = client.get_allocations(&auction_id).get(2).unwrap();
+    env.ledger().set_timestamp(allocation.dispute_deadline + 1);
+    client.finalize_allocation(&auction_id, &bidder3);
+
+    // The flat fee came out of both payouts to the seller, not the refund
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Completed);
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(balance(&env, &client, &treasury), 20);
+    assert_eq!(balance(&env, &client, &seller), 1200 - 10 + 2400 - 10);
+}
+
+// Test buy-it-now
+#[test]
+fn test_buy_now() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+    let buyer = Address::generate(&env);
+    fund(&env, &client, &buyer, 1_000_000);
+
+    // Buy now at 5000 while bidding stays at or below 2000
+    let mut options = default_options();
+    options.buy_now_price = 5000;
+    options.buy_now_threshold = 2000;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder, &1500, &1);
+
+    // Buying now refunds the current highest bidder and ends the auction
+    env.mock_all_auths();
+    client.buy_now(&auction_id, &buyer);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(auction.highest_bidder, buyer);
+    assert_eq!(auction.highest_bid_amount, 5000);
+    assert_eq!(auction.escrowed_amount, 5000);
+    assert_eq!(balance(&env, &client, &bidder), 1_000_000);
+    assert_eq!(balance(&env, &client, &buyer), 1_000_000 - 5000);
+}
+
+// Test that buy-it-now closes once bidding passes the threshold
+#[test]
+fn test_buy_now_threshold_exceeded() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+    let buyer = Address::generate(&env);
+    fund(&env, &client, &buyer, 1_000_000);
+
+    let mut options = default_options();
+    options.buy_now_price = 5000;
+    options.buy_now_threshold = 2000;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder, &2500, &1);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_buy_now(&auction_id, &buyer),
+        Err(Ok(AuctionError::BuyNowThresholdExceeded))
+    );
+
+    // Auctions without a buy now price reject it outright
+    let plain_id = create_test_auction(&env, &client, &seller);
+    let plain = client.get_auction(&plain_id).unwrap();
+    env.ledger().set_timestamp(plain.start_time);
+    env.mock_all_auths();
+    client.start_auction(&plain_id);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_buy_now(&plain_id, &buyer),
+        Err(Ok(AuctionError::BuyNowUnavailable))
+    );
+}
+
+// Test that bids must clear the minimum increment
+#[test]
+fn test_minimum_bid_increment() {
+    let (env, client, _admin, seller, bidder1) = setup_test();
+    let bidder2 = Address::generate(&env);
+    fund(&env, &client, &bidder2, 1_000_000);
+
+    // Raises must be at least 50, or 10% of the current bid if larger
+    let mut options = default_options();
+    options.bid_increment = 50;
+    options.bid_increment_bps = 1000;
+    options.starting_price = 100;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // The opening bid must meet the starting price
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder1, &99, &1),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &100, &1);
+
+    // Above 500 the basis points dominate the absolute increment
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder2, &149, &1),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder2, &1000, &1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder1, &1099, &1),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder1, &1100, &1);
+
+    // Increments above 100% are rejected at creation
+    options.bid_increment_bps = 10_001;
+    let name = String::from_str(&env, "Test Item");
+    let start_time = env.ledger().timestamp() + 100;
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_auction(
+            &seller,
+            &name,
+            &name,
+            &ProductCondition::Good,
+            &vec![&env],
+            &1,
+            &1000,
+            &start_time,
+            &(start_time + 3600),
+            &options,
+        ),
+        Err(Ok(AuctionError::InvalidBidIncrement))
+    );
+}
+
+// Test that the opening bid meets the reserve unless a lower starting price is set
+#[test]
+fn test_opening_bid_meets_reserve() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder, &1, &1),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder, &999, &1),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder, &1000, &1);
+
+    // A starting price above the reserve is rejected at creation
+    let mut options = default_options();
+    options.starting_price = 1001;
+    let name = String::from_str(&env, "Test Item");
+    let start_time = env.ledger().timestamp() + 100;
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_auction(
+            &seller,
+            &name,
+            &name,
+            &ProductCondition::Good,
+            &vec![&env],
+            &1,
+            &1000,
+            &start_time,
+            &(start_time + 3600),
+            &options,
+        ),
+        Err(Ok(AuctionError::InvalidReservePrice))
+    );
+}
+
+// Test that an auction ending below reserve refunds the bidder and cannot ship
+#[test]
+fn test_reserve_not_met() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+    let rival = Address::generate(&env);
+    fund(&env, &client, &rival, 1_000_000);
+
+    let mut options = default_options();
+    options.bid_increment = 100;
+    options.starting_price = 400;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // The leading proxy stays below the 1000 reserve
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &bidder, &900, &1);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &rival, &500, &1);
+    assert_eq!(client.get_visible_price(&auction_id), 600);
+
+    env.ledger().set_timestamp(auction.end_time + 1);
+    client.end_auction(&auction_id);
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(
+        auction.status,
+        crate::datatype::AuctionStatus::ReserveNotMet
+    );
+    assert_eq!(auction.escrowed_amount, 0);
+    assert_eq!(balance(&env, &client, &bidder), 1_000_000);
+    assert_eq!(balance(&env, &client, &rival), 1_000_000);
+
+    // An unsold item cannot be shipped or disputed
+    let text = String::from_str(&env, "n/a");
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_add_shipping_info(&auction_id, &text, &text, &0, &text),
+        Err(Ok(AuctionError::AuctionNotSold))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_open_dispute(&auction_id, &bidder, &text),
+        Err(Ok(AuctionError::DisputeNotAllowed))
+    );
+
+    // Auctions without any bid do not sell either
+    let empty_id = create_test_auction(&env, &client, &seller);
+    let empty = client.get_auction(&empty_id).unwrap();
+    env.ledger().set_timestamp(empty.start_time);
+    env.mock_all_auths();
+    client.start_auction(&empty_id);
+    env.ledger().set_timestamp(empty.end_time + 1);
+    client.end_auction(&empty_id);
+    assert_eq!(
+        client.get_auction(&empty_id).unwrap().status,
+        crate::datatype::AuctionStatus::ReserveNotMet
+    );
+}
+
+// Test that a proxy bid answers manual bids up to its hidden maximum
+#[test]
+fn test_proxy_bidding() {
+    let (env, client, _admin, seller, proxy_bidder) = setup_test();
+    let manual_bidder = Address::generate(&env);
+    fund(&env, &client, &manual_bidder, 1_000_000);
+
+    let mut options = default_options();
+    options.bid_increment = 100;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // The opening proxy bid shows only the reserve price, but escrows the maximum
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &proxy_bidder, &3000, &1);
+    assert_eq!(client.get_visible_price(&auction_id), 1000);
+    assert_eq!(balance(&env, &client, &proxy_bidder), 1_000_000 - 3000);
+
+    // A manual bid below the maximum is answered one increment higher
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &manual_bidder, &1500, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.highest_bidder, proxy_bidder);
+    assert_eq!(client.get_visible_price(&auction_id), 1600);
+    assert_eq!(balance(&env, &client, &manual_bidder), 1_000_000);
+
+    // A manual bid above the maximum takes the lead and releases the proxy
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &manual_bidder, &3500, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.highest_bidder, manual_bidder);
+    assert_eq!(client.get_visible_price(&auction_id), 3500);
+    assert_eq!(balance(&env, &client, &proxy_bidder), 1_000_000);
+    assert_eq!(auction.escrowed_amount, 3500);
+
+    // Proxy bidding needs an increment
+    let plain_id = create_test_auction(&env, &client, &seller);
+    let plain = client.get_auction(&plain_id).unwrap();
+    env.ledger().set_timestamp(plain.start_time);
+    env.mock_all_auths();
+    client.start_auction(&plain_id);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_proxy_bid(&plain_id, &proxy_bidder, &3000, &1),
+        Err(Ok(AuctionError::InvalidBidIncrement))
+    );
+}
+
+// Test that a manual bid from the leading proxy raises its maximum instead of
+// Test that a proxy whose maximum meets the reserve never shows a price below
+// it, even when a lower starting price is set
+#[test]
+fn test_proxy_visible_price_meets_reserve() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+    let rival = Address::generate(&env);
+    fund(&env, &client, &rival, 1_000_000);
+
+    let mut options = default_options();
+    options.bid_increment = 100;
+    options.starting_price = 500;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // A lone proxy above the reserve opens at the reserve, not the starting price
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &bidder, &3000, &1);
+    assert_eq!(client.get_visible_price(&auction_id), 1000);
+
+    env.ledger().set_timestamp(auction.end_time + 1);
+    client.end_auction(&auction_id);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(auction.highest_bidder, bidder);
+    assert_eq!(auction.escrowed_amount, 1000);
+    assert_eq!(balance(&env, &client, &bidder), 1_000_000 - 1000);
+
+    // A proxy answering a bid below the reserve also rises to it, and a
+    // proxy below the reserve keeps its maximum as the visible price
+    let second_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let second = client.get_auction(&second_id).unwrap();
+    env.ledger().set_timestamp(second.start_time);
+    env.mock_all_auths();
+    client.start_auction(&second_id);
+    env.mock_all_auths();
+    client.place_proxy_bid(&second_id, &bidder, &800, &1);
+    assert_eq!(client.get_visible_price(&second_id), 500);
+    env.mock_all_auths();
+    client.place_bid(&second_id, &rival, &600, &1);
+    assert_eq!(client.get_visible_price(&second_id), 700);
+
+    // Raising the maximum past the reserve lifts the visible price to it
+    env.mock_all_auths();
+    client.place_proxy_bid(&second_id, &bidder, &2000, &1);
+    assert_eq!(client.get_visible_price(&second_id), 1000);
+    env.mock_all_auths();
+    client.place_bid(&second_id, &rival, &1100, &1);
+    assert_eq!(client.get_visible_price(&second_id), 1200);
+}
+
+// bidding the visible price up against itself
+#[test]
+fn test_leader_bid_raises_proxy_maximum() {
+    let (env, client, _admin, seller, proxy_bidder) = setup_test();
+    let rival = Address::generate(&env);
+    fund(&env, &client, &rival, 1_000_000);
+
+    let mut options = default_options();
+    options.bid_increment = 100;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &proxy_bidder, &3000, &1);
+    assert_eq!(client.get_visible_price(&auction_id), 1000);
+
+    // A bid below the leader's own maximum is rejected and changes nothing
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &proxy_bidder, &2000, &1),
+        Err(Ok(AuctionError::ProxyMaxTooLow))
+    );
+    assert_eq!(client.get_visible_price(&auction_id), 1000);
+
+    // A bid above it becomes the new maximum; the visible price stays put
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &proxy_bidder, &4000, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.highest_bidder, proxy_bidder);
+    assert_eq!(auction.highest_bid_amount, 1000);
+    assert_eq!(auction.escrowed_amount, 4000);
+    assert_eq!(balance(&env, &client, &proxy_bidder), 1_000_000 - 4000);
+
+    // The raised maximum answers a rival above the old one
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &rival, &3500, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.highest_bidder, proxy_bidder);
+    assert_eq!(auction.highest_bid_amount, 3600);
+    assert_eq!(balance(&env, &client, &rival), 1_000_000);
+}
+
+// Test that competing proxies resolve deterministically and the winner pays the visible price
+#[test]
+fn test_competing_proxy_bids() {
+    let (env, client, _admin, seller, first) = setup_test();
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    fund(&env, &client, &second, 1_000_000);
+    fund(&env, &client, &third, 1_000_000);
+
+    let mut options = default_options();
+    options.bid_increment = 100;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &first, &2000, &1);
+
+    // An equal maximum loses to the earlier proxy
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &second, &2000, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.highest_bidder, first);
+    assert_eq!(client.get_visible_price(&auction_id), 2000);
+    assert_eq!(balance(&env, &client, &second), 1_000_000);
+
+    // A higher maximum leads one increment above the old ceiling
+    env.mock_all_auths();
+    client.place_proxy_bid(&auction_id, &third, &5000, &1);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.highest_bidder, third);
+    assert_eq!(client.get_visible_price(&auction_id), 2100);
+    assert_eq!(balance(&env, &client, &first), 1_000_000);
+
+    // The winner is refunded the unused part of their maximum
+    env.ledger().set_timestamp(auction.end_time + 1);
+    env.mock_all_auths();
+    client.end_auction(&auction_id);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.escrowed_amount, 2100);
+    assert_eq!(balance(&env, &client, &third), 1_000_000 - 2100);
+}
+
+// Test paginated discovery queries
+#[test]
+fn test_paginated_discovery() {
+    let (env, client, _admin, seller, _bidder) = setup_test();
+
+    // Three auctions; the first two are started
+    let first = create_test_auction(&env, &client, &seller);
+    let second = create_test_auction(&env, &client, &seller);
+    let third = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&first).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&first);
+    env.mock_all_auths();
+    client.start_auction(&second);
+
+    let page = client.get_active_auctions(&0, &10);
+    assert_eq!(page.auction_ids, vec![&env, first.clone(), second.clone()]);
+    assert!(!page.has_more);
+
+    // A limit of one walks the results a page at a time
+    let page = client.get_active_auctions(&0, &1);
+    assert_eq!(page.auction_ids, vec![&env, first.clone()]);
+    assert!(page.has_more);
+    let page = client.get_active_auctions(&page.next_cursor, &1);
+    assert_eq!(page.auction_ids, vec![&env, second.clone()]);
+
+    let page = client.get_auctions_by_status(&crate::datatype::AuctionStatus::Pending, &0, &10);
+    assert_eq!(page.auction_ids, vec![&env, third.clone()]);
+
+    // Everything created here ends at the same time
+    let page = client.get_auctions_ending_before(&auction.end_time, &0, &10);
+    assert_eq!(page.auction_ids.len(), 0);
+    let page = client.get_auctions_ending_before(&(auction.end_time + 1), &0, &10);
+    assert_eq!(page.auction_ids.len(), 3);
+}
+
+// Test that bid history is stored in pages
+#[test]
+fn test_paged_bid_history() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    for i in 0..52 {
+        env.mock_all_auths();
+        client.place_bid(&auction_id, &bidder, &(1000 + i), &1);
+    }
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.bid_count, 52);
+    assert_eq!(client.get_bids(&auction_id, &0).len(), 50);
+    let last_page = client.get_bids(&auction_id, &1);
+    assert_eq!(last_page.len(), 2);
+    assert_eq!(last_page.get(1).unwrap().amount, 1051);
+    assert_eq!(client.get_bids(&auction_id, &2).len(), 0);
+}
+
+// Test the per-bidder index and the winning, outbid and won queries
+#[test]
+fn test_user_bid_index() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+    let rival = Address::generate(&env);
+    fund(&env, &client, &rival, 1_000_000);
+
+    let first = create_test_auction(&env, &client, &seller);
+    let second = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&first).unwrap();
+    env.ledger().set_timestamp(auction.start_time + 10);
+    env.mock_all_auths();
+    client.start_auction(&first);
+    env.mock_all_auths();
+    client.start_auction(&second);
+
+    // The bidder leads both auctions, then is overtaken on the first
+    env.mock_all_auths();
+    client.place_bid(&first, &bidder, &1500, &1);
+    env.mock_all_auths();
+    client.place_bid(&second, &bidder, &1200, &1);
+    env.ledger().set_timestamp(auction.start_time + 20);
+    env.mock_all_auths();
+    client.place_bid(&first, &rival, &2000, &1);
+
+    assert_eq!(
+        client.get_user_bidding_auctions(&bidder),
+        vec![&env, first.clone(), second.clone()]
+    );
+    let record = client.get_user_bid(&first, &bidder).unwrap();
+    assert_eq!(record.amount, 1500);
+    assert_eq!(record.timestamp, auction.start_time + 10);
+    assert!(!record.is_winning);
+    assert!(client.get_user_bid(&first, &rival).unwrap().is_winning);
+    assert!(client.get_user_bid(&first, &seller).is_none());
+
+    let winning = client.get_user_winning_auctions(&bidder, &0, &10);
+    assert_eq!(winning.auction_ids, vec![&env, second.clone()]);
+    assert!(!winning.has_more);
+    let outbid = client.get_user_outbid_auctions(&bidder, &0, &10);
+    assert_eq!(outbid.auction_ids, vec![&env, first.clone()]);
+    assert_eq!(
+        client
+            .get_user_won_auctions(&bidder, &0, &10)
+            .auction_ids
+            .len(),
+        0
+    );
+
+    // Pages follow the order the bidder first bid
+    let page = client.get_user_outbid_auctions(&bidder, &0, &1);
+    assert_eq!(page.next_cursor, 1);
+    assert!(page.has_more);
+    let page = client.get_user_outbid_auctions(&bidder, &page.next_cursor, &1);
+    assert_eq!(page.auction_ids.len(), 0);
+    assert!(!page.has_more);
+
+    // Once both end, the leads become wins
+    env.ledger().set_timestamp(auction.end_time + 10);
+    client.end_auction(&first);
+    client.end_auction(&second);
+    assert_eq!(
+        client.get_user_won_auctions(&bidder, &0, &10).auction_ids,
+        vec![&env, second.clone()]
+    );
+    assert_eq!(
+        client.get_user_won_auctions(&rival, &0, &10).auction_ids,
+        vec![&env, first]
+    );
+    assert_eq!(
+        client
+            .get_user_winning_auctions(&bidder, &0, &10)
+            .auction_ids
+            .len(),
+        0
+    );
+    assert!(client.get_user_bid(&second, &bidder).unwrap().is_winning);
+}
+
+// Test that a Pending auction reads as Expired once its end time passes,
+// without anyone calling end_auction
+#[test]
+fn test_pending_auction_expires_on_read() {
+    let (env, client, _admin, seller, _bidder) = setup_test();
+
+    let auction_id = create_test_auction(&env, &client, &seller);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.end_time - 1);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().status,
+        crate::datatype::AuctionStatus::Pending
+    );
+
+    env.ledger().set_timestamp(auction.end_time);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().status,
+        crate::datatype::AuctionStatus::Expired
+    );
+    assert_eq!(
+        client
+            .get_auctions(&vec![&env, auction_id.clone()])
+            .get(0)
+            .unwrap()
+            .status,
+        crate::datatype::AuctionStatus::Expired
+    );
+    let expired = client.get_auctions_by_status(&crate::datatype::AuctionStatus::Expired, &0, &10);
+    assert_eq!(expired.auction_ids, vec![&env, auction_id.clone()]);
+    let pending = client.get_auctions_by_status(&crate::datatype::AuctionStatus::Pending, &0, &10);
+    assert_eq!(pending.auction_ids.len(), 0);
+}
+
+// Test that stale Pending auctions expire and unsold auctions can be relisted
+#[test]
+fn test_expiry_and_relist() {
+    let (env, client, _admin, seller, bidder) = setup_test();
+
+    // The seller never starts the auction; bids may open below the reserve
+    let mut options = default_options();
+    options.starting_price = 500;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let original = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(original.end_time);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_start_auction(&auction_id),
+        Err(Ok(AuctionError::AuctionAlreadyEnded))
+    );
+    client.end_auction(&auction_id);
+    let expired = client.get_auction(&auction_id).unwrap();
+    assert_eq!(expired.status, crate::datatype::AuctionStatus::Expired);
+
+    // Relisting keeps the product and options, with new times and reserve
+    let start_time = original.end_time + 100;
+    let end_time = start_time + 3600;
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_relist_auction(&auction_id, &end_time, &start_time, &0),
+        Err(Ok(AuctionError::InvalidTimeRange))
+    );
+    env.mock_all_auths();
+    let relisted_id = client.relist_auction(&auction_id, &start_time, &end_time, &800);
+    let relisted = client.get_auction(&relisted_id).unwrap();
+    assert_eq!(relisted.status, crate::datatype::AuctionStatus::Pending);
+    assert_eq!(relisted.product.id, original.product.id);
+    assert_eq!(relisted.product.name, original.product.name);
+    assert_eq!(relisted.reserve_price, 800);
+    assert!(relisted.has_relisted_from);
+    assert_eq!(relisted.relisted_from, auction_id);
+    assert_eq!(
+        client.get_product_lineage(&original.product.id),
+        vec![&env, auction_id.clone(), relisted_id.clone()]
+    );
+    assert!(client
+        .get_user_selling_auctions(&seller)
+        .contains(&relisted_id));
+
+    // Only the latest listing can be relisted, and not while it is running
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_relist_auction(&auction_id, &start_time, &end_time, &0),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    env.ledger().set_timestamp(start_time);
+    env.mock_all_auths();
+    client.start_auction(&relisted_id);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_relist_auction(&relisted_id, &end_time, &(end_time + 3600), &0),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+
+    // A bid below the new reserve does not sell, so the product goes round again
+    env.mock_all_auths();
+    client.place_bid(&relisted_id, &bidder, &500, &1);
+    env.ledger().set_timestamp(end_time);
+    client.end_auction(&relisted_id);
+    env.mock_all_auths();
+    let third_id = client.relist_auction(&relisted_id, &(end_time + 10), &(end_time + 3600), &0);
+    assert_eq!(client.get_auction(&third_id).unwrap().reserve_price, 800);
+
+    // A stale Pending listing can be relisted directly; it expires on the way
+    let third = client.get_auction(&third_id).unwrap();
+    env.ledger().set_timestamp(third.end_time + 1);
+    env.mock_all_auths();
+    let fourth_id = client.relist_auction(
+        &third_id,
+        &(third.end_time + 100),
+        &(third.end_time + 3700),
+        &0,
+    );
+    assert_eq!(
+        client.get_auction(&third_id).unwrap().status,
+        crate::datatype::AuctionStatus::Expired
+    );
+    assert_eq!(client.get_product_lineage(&original.product.id).len(), 4);
+    assert_eq!(
+        client.get_auction(&fourth_id).unwrap().relisted_from,
+        third_id
+    );
+
+    // A sold auction cannot be relisted
+    let sold_id = create_test_auction(&env, &client, &seller);
+    let sold = client.get_auction(&sold_id).unwrap();
+    env.ledger().set_timestamp(sold.start_time);
+    env.mock_all_auths();
+    client.start_auction(&sold_id);
+    env.mock_all_auths();
+    client.place_bid(&sold_id, &bidder, &1500, &1);
+    env.ledger().set_timestamp(sold.end_time);
+    client.end_auction(&sold_id);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_relist_auction(&sold_id, &(sold.end_time + 10), &(sold.end_time + 3600), &0),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+}
+
+// Test that a quorum of verifier attestations authenticates a product and gates bidding
+#[test]
+fn test_attestation_quorum() {
+    let (env, client, admin, seller, bidder) = setup_test();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    for verifier in [&first, &second] {
+        env.mock_all_auths();
+        client.add_verifier(&admin, verifier);
+    }
+    let notes = BytesN::from_array(&env, &[7; 32]);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_set_attestation_quorum(&admin, &0),
+        Err(Ok(AuctionError::InvalidConfig))
+    );
+    env.mock_all_auths();
+    client.set_attestation_quorum(&admin, &2);
+    assert_eq!(client.get_attestation_quorum(), 2);
+
+    // The seller only takes bids once the product is authenticated
+    let mut options = default_options();
+    options.require_authentication = true;
+    let auction_id = create_test_auction_with_options(&env, &client, &seller, &options);
+    let auction = client.get_auction(&auction_id).unwrap();
+    env.ledger().set_timestamp(auction.start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+
+    // One positive attestation is below quorum
+    env.mock_all_auths();
+    client.verify_product(&first, &auction_id, &true, &notes);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder, &1500, &1),
+        Err(Ok(AuctionError::AuctionNotActive))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_verify_product(&seller, &auction_id, &true, &notes),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+
+    // A second verifier reaches quorum
+    env.ledger().set_timestamp(auction.start_time + 10);
+    env.mock_all_auths();
+    client.verify_product(&second, &auction_id, &true, &notes);
+    let product = client.get_auction(&auction_id).unwrap().product;
+    assert!(product.is_authenticated);
+    assert_eq!(product.attestations.len(), 2);
+    let attestation = product.attestations.get(1).unwrap();
+    assert_eq!(attestation.verifier, second);
+    assert!(attestation.is_authentic);
+    assert_eq!(attestation.timestamp, auction.start_time + 10);
+    assert_eq!(attestation.notes_hash, notes);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &bidder, &1500, &1);
+
+    // A verifier changing their verdict supersedes their earlier one; both
+    // entries stay on the trail
+    env.ledger().set_timestamp(auction.start_time + 20);
+    let flipped_notes = BytesN::from_array(&env, &[8; 32]);
+    env.mock_all_auths();
+    client.verify_product(&first, &auction_id, &false, &flipped_notes);
+    let product = client.get_auction(&auction_id).unwrap().product;
+    assert!(!product.is_authenticated);
+    assert_eq!(product.attestations.len(), 3);
+    let earlier = product.attestations.get(0).unwrap();
+    assert_eq!(earlier.verifier, first);
+    assert!(earlier.is_authentic);
+    assert_eq!(earlier.notes_hash, notes);
+    let flipped = product.attestations.get(2).unwrap();
+    assert_eq!(flipped.verifier, first);
+    assert!(!flipped.is_authentic);
+    assert_eq!(flipped.timestamp, auction.start_time + 20);
+    assert_eq!(flipped.notes_hash, flipped_notes);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder, &2000, &1),
+        Err(Ok(AuctionError::AuctionNotActive))
+    );
+
+    // Repeating a positive verdict does not count twice
+    env.mock_all_auths();
+    client.verify_product(&second, &auction_id, &true, &notes);
+    let product = client.get_auction(&auction_id).unwrap().product;
+    assert!(!product.is_authenticated);
+    assert_eq!(product.attestations.len(), 4);
+
+    // Removing a verifier revokes authentication without a new attestation
+    env.mock_all_auths();
+    client.verify_product(&first, &auction_id, &true, &notes);
+    assert!(
+        client
+            .get_auction(&auction_id)
+            .unwrap()
+            .product
+            .is_authenticated
+    );
+    env.mock_all_auths();
+    client.remove_verifier(&admin, &second);
+    assert!(
+        !client
+            .get_auction(&auction_id)
+            .unwrap()
+            .product
+            .is_authenticated
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder, &2000, &1),
+        Err(Ok(AuctionError::AuctionNotActive))
+    );
+
+    // So does raising the quorum
+    env.mock_all_auths();
+    client.add_verifier(&admin, &second);
+    assert!(
+        client
+            .get_auction(&auction_id)
+            .unwrap()
+            .product
+            .is_authenticated
+    );
+    env.mock_all_auths();
+    client.set_attestation_quorum(&admin, &3);
+    assert!(
+        !client
+            .get_auction(&auction_id)
+            .unwrap()
+            .product
+            .is_authenticated
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&auction_id, &bidder, &2000, &1),
+        Err(Ok(AuctionError::AuctionNotActive))
+    );
+
+    // Without the flag, bidding does not wait for authentication
+    let open_id = create_test_auction(&env, &client, &seller);
+    let open = client.get_auction(&open_id).unwrap();
+    env.ledger().set_timestamp(open.start_time);
+    env.mock_all_auths();
+    client.start_auction(&open_id);
+    env.mock_all_auths();
+    client.place_bid(&open_id, &bidder, &1500, &1);
+}
+
+// Helper function to describe one item of a lot
+fn lot_item(env: &Env, name: &str, condition: ProductCondition) -> LotItemInput {
+    LotItemInput {
+        name: String::from_str(env, name),
+        description: String::from_str(env, "Part of a teaching bundle"),
+        condition,
+        images: vec![env, String::from_str(env, "https://example.com/item.jpg")],
+    }
+}
+
+// Test a lot auction with per-item verification and delivery
+#[test]
+fn test_lot_auction() {
+    let (env, client, admin, seller, buyer) = setup_test();
+    let verifier = Address::generate(&env);
+    env.mock_all_auths();
+    client.add_verifier(&admin, &verifier);
+    let notes = BytesN::from_array(&env, &[3; 32]);
+
+    let name = String::from_str(&env, "Chemistry bundle");
+    let start_time = env.ledger().timestamp() + 100;
+    let end_time = start_time + 3600;
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_lot_auction(
+            &seller,
+            &name,
+            &name,
+            &vec![&env],
+            &1000,
+            &start_time,
+            &end_time,
+            &default_options(),
+        ),
+        Err(Ok(AuctionError::InvalidInventory))
+    );
+
+    let items = vec![
+        &env,
+        lot_item(&env, "Textbook", ProductCondition::LikeNew),
+        lot_item(&env, "Microscope", ProductCondition::Fair),
+        lot_item(&env, "Lab coat", ProductCondition::New),
+    ];
+    env.mock_all_auths();
+    let auction_id = client.create_lot_auction(
+        &seller,
+        &name,
+        &name,
+        &items,
+        &1000,
+        &start_time,
+        &end_time,
+        &default_options(),
+    );
+
+    // The lot is one unit described by its worst item; queries return the breakdown
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.lot_size, 3);
+    assert_eq!(auction.product.inventory_count, 1);
+    assert_eq!(auction.product.condition, ProductCondition::Fair);
+    assert_eq!(auction.product.images.len(), 3);
+    let lot = client.get_lot(&auction_id);
+    assert_eq!(lot.len(), 3);
+    assert_eq!(
+        lot.get(1).unwrap().product.name,
+        String::from_str(&env, "Microscope")
+    );
+    assert_ne!(
+        lot.get(0).unwrap().product.id,
+        lot.get(1).unwrap().product.id
+    );
+    let single_id = create_test_auction(&env, &client, &seller);
+    assert_eq!(
+        client.try_get_lot(&single_id).err(),
+        Some(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // Items are verified one by one; the lot is authenticated once all are
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_verify_product(&verifier, &auction_id, &true, &notes),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_verify_lot_item(&verifier, &auction_id, &3, &true, &notes),
+        Err(Ok(AuctionError::InvalidInventory))
+    );
+    for index in 0..3u32 {
+        assert!(
+            !client
+                .get_auction(&auction_id)
+                .unwrap()
+                .product
+                .is_authenticated
+        );
+        env.mock_all_auths();
+        client.verify_lot_item(&verifier, &auction_id, &index, &true, &notes);
+    }
+    assert!(
+        client
+            .get_auction(&auction_id)
+            .unwrap()
+            .product
+            .is_authenticated
+    );
+    assert!(
+        client
+            .get_lot(&auction_id)
+            .get(2)
+            .unwrap()
+            .product
+            .is_authenticated
+    );
+
+    // Sell the lot; shipping is quoted for every item in it
+    env.ledger().set_timestamp(start_time);
+    env.mock_all_auths();
+    client.start_auction(&auction_id);
+    env.mock_all_auths();
+    client.place_bid(&auction_id, &buyer, &1500, &1);
+    env.ledger().set_timestamp(end_time);
+    client.end_auction(&auction_id);
+
+    let carrier = setup_free_shipping(&env, &client, &admin);
+    let zone = String::from_str(&env, "LOCAL");
+    env.mock_all_auths();
+    client.set_shipping_rates(
+        &admin,
+        &ShippingRates {
+            speed_multipliers: map![&env, (1, 10_000)],
+            quantity_tiers: vec![
+                &env,
+                QuantityTier {
+                    min_quantity: 1,
+                    unit_cost: 40,
+                },
+            ],
+            bulk_discounts: vec![&env],
+        },
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.quote_shipping(&auction_id, &buyer, &zone, &1, &carrier),
+        120
+    );
+
+    // Shipping the lot ships every item
+    let text = String::from_str(&env, "TRK-LOT");
+    env.mock_all_auths();
+    client.add_shipping_info(&auction_id, &text, &text, &0, &text);
+    for item in client.get_lot(&auction_id).iter() {
+        assert_eq!(item.shipping_status, ShippingStatus::Shipped);
+    }
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_shipping_status(&auction_id, &carrier, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_delivery(&auction_id, &buyer),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // Items arrive separately; the lot follows its least advanced item
+    env.mock_all_auths();
+    client.update_lot_item_status(&auction_id, &carrier, &0, &ShippingStatus::Delivered);
+    env.mock_all_auths();
+    client.confirm_lot_item_delivery(&auction_id, &buyer, &1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_update_lot_item_status(&auction_id, &seller, &2, &ShippingStatus::Delivered),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    env.mock_all_auths();
+    client.update_lot_item_status(&auction_id, &seller, &2, &ShippingStatus::InTransit);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.shipping_status, ShippingStatus::InTransit);
+    assert_eq!(
+        client.try_finalize_delivery(&auction_id),
+        Err(Ok(AuctionError::NotDelivered))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_lot_item_delivery(&auction_id, &buyer, &0),
+        Err(Ok(AuctionError::InvalidShippingTransition))
+    );
+
+    // The last delivery completes the lot's delivery and opens the dispute window
+    env.mock_all_auths();
+    client.update_lot_item_status(&auction_id, &carrier, &2, &ShippingStatus::Delivered);
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction.shipping_status, ShippingStatus::Delivered);
+    let lot = client.get_lot(&auction_id);
+    assert!(lot
+        .iter()
+        .all(|item| item.shipping_status == ShippingStatus::Delivered));
+    assert_eq!(lot.get(1).unwrap().delivered_at, end_time);
+
+    env.ledger().set_timestamp(auction.dispute_deadline + 1);
+    client.finalize_delivery(&auction_id);
+    assert_eq!(
+        client.get_auction(&auction_id).unwrap().status,
+        crate::datatype::AuctionStatus::Completed
+    );
+    assert_eq!(balance(&env, &client, &seller), 1500 + 120);
+}
+
+// Test a reverse auction: educators offer within a learner's budget and the
+// learner accepts any offer, then delivery and disputes follow the usual flow
+#[test]
+fn test_service_request() {
+    let (env, client, admin, _seller, learner) = setup_test();
+    let educator = Address::generate(&env);
+    let rival = Address::generate(&env);
+    let subject = String::from_str(&env, "Calculus tutoring");
+    let description = String::from_str(&env, "Four sessions on integrals");
+    let now = env.ledger().timestamp();
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_service_request(&learner, &subject, &description, &1000, &now),
+        Err(Ok(AuctionError::InvalidTimeRange))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_service_request(&learner, &subject, &description, &0, &(now + 3600)),
+        Err(Ok(AuctionError::InvalidReservePrice))
+    );
+    let mut options = default_options();
+    options.auction_type = AuctionType::Reverse;
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_auction(
+            &learner,
+            &subject,
+            &description,
+            &ProductCondition::New,
+            &vec![&env],
+            &1,
+            &1000,
+            &now,
+            &(now + 3600),
+            &options,
+        ),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // The request opens for offers as soon as it is posted
+    env.mock_all_auths();
+    let request_id =
+        client.create_service_request(&learner, &subject, &description, &1000, &(now + 3600));
+    let request = client.get_auction(&request_id).unwrap();
+    assert_eq!(request.status, crate::datatype::AuctionStatus::Active);
+    assert_eq!(request.reserve_price, 1000);
+    assert_eq!(request.product.seller, learner);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_bid(&request_id, &educator, &900, &1),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+
+    // Offers stay within budget and each educator can only lower their own
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_offer(&request_id, &educator, &1100),
+        Err(Ok(AuctionError::InvalidAmount))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_offer(&request_id, &learner, &500),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    env.mock_all_auths();
+    client.place_offer(&request_id, &educator, &900);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_offer(&request_id, &educator, &900),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    env.mock_all_auths();
+    client.place_offer(&request_id, &educator, &850);
+    env.mock_all_auths();
+    client.place_offer(&request_id, &rival, &700);
+    assert_eq!(client.get_bids(&request_id, &0).len(), 3);
+    assert_eq!(balance(&env, &client, &learner), 1_000_000);
+
+    // The learner accepts a higher offer than the lowest one
+    let stranger = Address::generate(&env);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_accept_offer(&request_id, &stranger),
+        Err(Ok(AuctionError::NoCommitment))
+    );
+    env.mock_all_auths();
+    client.accept_offer(&request_id, &educator);
+    let request = client.get_auction(&request_id).unwrap();
+    assert_eq!(request.status, crate::datatype::AuctionStatus::Ended);
+    assert_eq!(request.product.seller, educator);
+    assert_eq!(request.highest_bidder, learner);
+    assert_eq!(request.escrowed_amount, 850);
+    assert_eq!(balance(&env, &client, &learner), 1_000_000 - 850);
+    assert_eq!(
+        client.get_user_won_auctions(&educator, &0, &10).auction_ids,
+        vec![&env, request_id.clone()]
+    );
+    assert_eq!(
+        client.get_user_outbid_auctions(&rival, &0, &10).auction_ids,
+        vec![&env, request_id.clone()]
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_offer(&request_id, &rival, &600),
+        Err(Ok(AuctionError::AuctionNotActive))
+    );
+
+    // Services are not shipped; the learner confirms delivery directly
+    let carrier = setup_free_shipping(&env, &client, &admin);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_quote_shipping(
+            &request_id,
+            &learner,
+            &String::from_str(&env, "LOCAL"),
+            &1,
+            &carrier
+        ),
+        Err(Ok(AuctionError::UnsupportedAuctionType))
+    );
+    env.mock_all_auths();
+    client.confirm_delivery(&request_id, &learner);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_confirm_delivery(&request_id, &learner),
+        Err(Ok(AuctionError::InvalidShippingTransition))
+    );
+
+    // The learner disputes the sessions and the ruling refunds them
+    let resolver = Address::generate(&env);
+    env.mock_all_auths();
+    client.add_resolver(&admin, &resolver);
+    env.mock_all_auths();
+    client.open_dispute(
+        &request_id,
+        &learner,
+        &String::from_str(&env, "Sessions missed"),
+    );
+    env.mock_all_auths();
+    client.submit_evidence(
+        &request_id,
+        &educator,
+        &String::from_str(&env, "Learner cancelled"),
+        &vec![&env],
+    );
+    env.mock_all_auths();
+    client.resolve_dispute(&resolver, &request_id, &DisputeResolution::RefundBuyer);
+    let case = client.get_dispute(&request_id);
+    env.ledger()
+        .set_timestamp(case.rulings.get(0).unwrap().timestamp + 3 * 24 * 60 * 60);
+    client.finalize_dispute(&request_id);
+    assert_eq!(balance(&env, &client, &learner), 1_000_000);
+    assert_eq!(balance(&env, &client, &educator), 0);
+
+    // A request nobody accepts ends unsold at its deadline
+    let now = env.ledger().timestamp();
+    env.mock_all_auths();
+    let unsold_id =
+        client.create_service_request(&learner, &subject, &description, &500, &(now + 60));
+    env.mock_all_auths();
+    client.place_offer(&unsold_id, &rival, &400);
+    env.ledger().set_timestamp(now + 60);
+    client.end_auction(&unsold_id);
+    assert_eq!(
+        client.get_auction(&unsold_id).unwrap().status,
+        crate::datatype::AuctionStatus::ReserveNotMet
+    );
+}
+
+// Test that an offer cannot be accepted once the request's deadline has passed
+#[test]
+fn test_service_request_deadline() {
+    let (env, client, _admin, _seller, learner) = setup_test();
+    let educator = Address::generate(&env);
+    let subject = String::from_str(&env, "Essay feedback");
+    let deadline = env.ledger().timestamp() + 3600;
+
+    env.mock_all_auths();
+    let request_id = client.create_service_request(&learner, &subject, &subject, &1000, &deadline);
+    env.mock_all_auths();
+    client.place_offer(&request_id, &educator, &800);
+
+    // The deadline closes acceptance as it closes offers
+    env.ledger().set_timestamp(deadline + 1);
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_place_offer(&request_id, &educator, &700),
+        Err(Ok(AuctionError::AuctionAlreadyEnded))
+    );
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_accept_offer(&request_id, &educator),
+        Err(Ok(AuctionError::AuctionAlreadyEnded))
+    );
+
+    // The request ends unaccepted and the learner paid nothing
+    client.end_auction(&request_id);
+    let request = client.get_auction(&request_id).unwrap();
+    assert_eq!(
+        request.status,
+        crate::datatype::AuctionStatus::ReserveNotMet
+    );
+    assert_eq!(request.product.seller, learner);
+    assert_eq!(request.escrowed_amount, 0);
+    assert_eq!(balance(&env, &client, &learner), 1_000_000);
 }